@@ -0,0 +1,125 @@
+//! Measures the hot paths of filtering words, comparing patterns computed from precomputed letters
+//! with patterns computed by counting characters in a map, as they used to be, and late-game
+//! rankings with and without the locked positions of the constraints.
+//!
+//! ```console
+//! $ cargo bench --bench matching
+//! ```
+
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use wools::{
+    load_default_words, rank_guesses, rank_guesses_with_constraints, solve, Constraints, Hint,
+    Pattern, Word,
+};
+
+fn hints_from_chars(solution: &Word, guess: &Word) -> Vec<Hint> {
+    let mut hints = vec![None; guess.length()];
+    let mut solution_chars: HashMap<char, usize> = HashMap::new();
+
+    for c in solution.chars() {
+        *solution_chars.entry(c).or_default() += 1;
+    }
+
+    for (i, (guess_char, solution_char)) in guess.chars().zip(solution.chars()).enumerate() {
+        if guess_char == solution_char {
+            *solution_chars.get_mut(&guess_char).unwrap() -= 1;
+            hints[i] = Some(Hint::Green);
+        }
+    }
+
+    for (i, guess_char) in guess.chars().enumerate() {
+        if hints[i].is_none() {
+            hints[i] = Some(match solution_chars.get_mut(&guess_char) {
+                Some(0) | None => Hint::Black,
+                Some(count) => {
+                    *count -= 1;
+                    Hint::Yellow
+                }
+            });
+        }
+    }
+
+    hints.into_iter().map(Option::unwrap).collect()
+}
+
+fn patterns(c: &mut Criterion) {
+    let words = load_default_words();
+    let guess = Word::new("soare");
+    let mut group = c.benchmark_group("patterns");
+
+    group.bench_function("letters", |b| {
+        b.iter(|| {
+            for solution in &words {
+                black_box(Pattern::from_solution_and_guess(solution, &guess));
+            }
+        })
+    });
+    group.bench_function("chars", |b| {
+        b.iter(|| {
+            for solution in &words {
+                black_box(hints_from_chars(solution, &guess));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn constraints(c: &mut Criterion) {
+    let words = load_default_words();
+    let solution = Word::new("fight");
+    let constraints = ["soare", "unlit"].map(|guess| {
+        Constraints::from_pattern(&Pattern::from_solution_and_guess(
+            &solution,
+            &Word::new(guess),
+        ))
+    });
+
+    c.bench_function("constraints", |b| {
+        b.iter(|| {
+            words
+                .iter()
+                .filter(|word| {
+                    constraints
+                        .iter()
+                        .all(|constraints| constraints.matches(word))
+                })
+                .count()
+        })
+    });
+}
+
+fn rankings(c: &mut Criterion) {
+    let words = load_default_words();
+    let solution = Word::new("light");
+    let patterns = ["crane", "night"]
+        .map(|guess| Pattern::from_solution_and_guess(&solution, &Word::new(guess)));
+    let candidates = solve(
+        &words,
+        &patterns
+            .iter()
+            .map(|pattern| (pattern.guess.clone(), pattern.hints.clone()))
+            .collect::<Vec<_>>(),
+    );
+    let constraints = Constraints::merge(&patterns.each_ref().map(Constraints::from_pattern));
+    let mut group = c.benchmark_group("rankings");
+
+    group.bench_function("every position", |b| {
+        b.iter(|| black_box(rank_guesses(&words, &candidates)))
+    });
+    group.bench_function("unlocked positions", |b| {
+        b.iter(|| {
+            black_box(rank_guesses_with_constraints(
+                &words,
+                &candidates,
+                &constraints,
+            ))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, patterns, constraints, rankings);
+criterion_main!(benches);
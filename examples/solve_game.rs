@@ -0,0 +1,66 @@
+//! Plays an automated game against a solution, printing each guess with its hints.
+//!
+//! ```console
+//! $ cargo run --release --example solve_game -- mourn
+//! ```
+
+use wools::{
+    best_guess_adaptive, load_default_words, Game, GameStatus, Solver, Word,
+    DEFAULT_ENDGAME_THRESHOLD,
+};
+
+const OPENER: &str = "soare";
+
+fn main() -> Result<(), String> {
+    let solution = match std::env::args().nth(1) {
+        Some(solution) => Word::try_new(&solution)?,
+        None => Word::new("mourn"),
+    };
+    let game = play(solution)?;
+
+    match game.status() {
+        GameStatus::Won => println!("Solved in {} guesses", game.patterns().len()),
+        _ => println!("Not solved, the solution was {}", game.solution()),
+    }
+
+    Ok(())
+}
+
+/// Plays a game against the solution until it is over, printing each guess with its hints.
+fn play(solution: Word) -> Result<Game, String> {
+    let words = load_default_words();
+    let mut solver = Solver::new(&words);
+    let mut game = Game::new(solution);
+
+    while game.status() == GameStatus::InProgress {
+        // The opener is fixed, since ranking every word against the whole dictionary is slow.
+        let guess = if game.patterns().is_empty() {
+            Word::new(OPENER)
+        } else {
+            best_guess_adaptive(&words, solver.candidates(), DEFAULT_ENDGAME_THRESHOLD)
+                .ok_or("no candidate remains")?
+                .clone()
+        };
+        let pattern = game.guess(&guess)?;
+
+        println!("{} {}", pattern.to_emoji(), guess);
+        solver.observe(&pattern.guess, &pattern.hints);
+    }
+
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use wools::{GameStatus, Word};
+
+    use crate::play;
+
+    #[test]
+    fn when_play_then_game_is_won() {
+        let game = play(Word::new("mourn")).unwrap();
+
+        assert_eq!(GameStatus::Won, game.status());
+        assert_eq!(&Word::new("mourn"), game.solution());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::error::WordsError;
+
+/// The letters words may be made of, one character per tile, for Wordle variants played in other
+/// languages than English (see [`crate::Word::with_alphabet`]).
+///
+/// Letters from `a` to `z` always keep their index in the English alphabet, from 0 to 25, while
+/// the other letters take the indices left free, by order of their code point, so that words of
+/// every alphabet share the representation of [`crate::Word::letters`]. An alphabet has at most
+/// [`Alphabet::MAX_LETTERS`] letters.
+///
+/// Each letter is a single unicode scalar value, so tiles made of several letters, such as the
+/// Spanish `ll`, or of a letter and a combining accent, cannot be represented. Words should be
+/// written with precomposed characters, such as `é` rather than `e` followed by `◌́`.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::Alphabet;
+/// let spanish = Alphabet::new(('a'..='z').chain(['ñ'])).unwrap();
+///
+/// assert!(spanish.contains('ñ'));
+/// assert!(!spanish.contains('é'));
+/// assert!(Alphabet::english().contains('e'));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alphabet {
+    mask: u32,
+    others: Vec<(char, u8)>,
+}
+
+impl Alphabet {
+    /// The maximum number of letters of an alphabet, so that sets of letters fit in the 32-bit
+    /// masks of [`crate::Word::mask`].
+    pub const MAX_LETTERS: usize = 32;
+
+    /// Returns the English alphabet, from `a` to `z`, which is the alphabet of words created with
+    /// [`crate::Word::new`] or parsed from strings.
+    pub fn english() -> Self {
+        Alphabet {
+            mask: (1 << 26) - 1,
+            others: Vec::new(),
+        }
+    }
+
+    /// Creates an alphabet of the letters, which are lowercased. Letters given several times are
+    /// only kept once.
+    ///
+    /// Returns an error if a character is not alphabetic, or if there are more than
+    /// [`Alphabet::MAX_LETTERS`] letters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Alphabet, WordsError};
+    /// let russian = Alphabet::new("абвгдежзийклмнопрстуфхцчшщъыьэюя".chars()).unwrap();
+    ///
+    /// assert!(russian.contains('ж'));
+    /// assert_eq!(Err(WordsError::NonAlphabetic), Alphabet::new(['a', '1']));
+    /// assert!(Alphabet::new("абвгдеёжзийклмнопрстуфхцчшщъыьэюя".chars()).is_err());
+    /// ```
+    pub fn new<I: IntoIterator<Item = char>>(letters: I) -> Result<Self, WordsError> {
+        let mut letters = letters
+            .into_iter()
+            .map(|c| match c.to_lowercase().collect::<Vec<char>>()[..] {
+                [lowercase] if lowercase.is_alphabetic() => Ok(lowercase),
+                _ => Err(WordsError::NonAlphabetic),
+            })
+            .collect::<Result<Vec<char>, WordsError>>()?;
+        letters.sort_unstable();
+        letters.dedup();
+
+        if letters.len() > Alphabet::MAX_LETTERS {
+            return Err(WordsError::TooManyLetters {
+                max: Alphabet::MAX_LETTERS,
+                actual: letters.len(),
+            });
+        }
+
+        let (english, others): (Vec<char>, Vec<char>) =
+            letters.into_iter().partition(char::is_ascii_lowercase);
+        let mask = english
+            .iter()
+            .fold(0_u32, |mask, &c| mask | 1 << (c as u8 - b'a'));
+        let free = (0..Alphabet::MAX_LETTERS as u8).filter(|&letter| mask & 1 << letter == 0);
+
+        Ok(Alphabet {
+            mask,
+            others: others.into_iter().zip(free).collect(),
+        })
+    }
+
+    /// Returns whether the character is a letter of the alphabet. Characters are not lowercased.
+    pub fn contains(&self, c: char) -> bool {
+        self.letter(c).is_some()
+    }
+
+    /// Returns the index of the letter, as stored in [`crate::Word::letters`], or `None` if the
+    /// character is not a letter of the alphabet.
+    pub(crate) fn letter(&self, c: char) -> Option<u8> {
+        if c.is_ascii_lowercase() {
+            Some(c as u8 - b'a').filter(|letter| self.mask & 1 << letter != 0)
+        } else {
+            self.others
+                .iter()
+                .find(|(other, _)| *other == c)
+                .map(|&(_, letter)| letter)
+        }
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::english()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alphabet::Alphabet;
+
+    #[test]
+    fn given_english_letters_when_new_then_keep_their_index() {
+        let spanish = Alphabet::new(('a'..='z').chain(['ñ'])).unwrap();
+
+        assert_eq!(Some(0), spanish.letter('a'));
+        assert_eq!(Some(25), spanish.letter('z'));
+        assert_eq!(Some(26), spanish.letter('ñ'));
+    }
+
+    #[test]
+    fn given_other_letters_when_new_then_take_the_free_indices() {
+        let alphabet = Alphabet::new(['b', 'Ж', 'ж', 'я', 'a']).unwrap();
+
+        assert_eq!(Some(0), alphabet.letter('a'));
+        assert_eq!(Some(1), alphabet.letter('b'));
+        assert_eq!(Some(2), alphabet.letter('ж'));
+        assert_eq!(Some(3), alphabet.letter('я'));
+        assert_eq!(None, alphabet.letter('c'));
+        assert_eq!(None, alphabet.letter('Ж'));
+    }
+}
@@ -0,0 +1,325 @@
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use crate::pattern::{Hint, Pattern};
+use crate::word::Word;
+
+/// Finds the pairs of candidates which produce the same hints for every allowed guess.
+///
+/// Such pairs cannot be told apart by any guess: once only the two words remain, the solution can
+/// only be found by guessing one of them and hoping for the best. A candidate which is also an
+/// allowed guess is always distinguishable, since guessing it produces an all-green pattern.
+///
+/// This computes `candidates × allowed` patterns, and compares them for every pair of candidates,
+/// for a total cost of `O(candidates² × allowed)`. It is meant for small candidate sets.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{indistinguishable_pairs, Word};
+/// let allowed = [Word::new("crane"), Word::new("pouty")];
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("stare")];
+/// let pairs = indistinguishable_pairs(&allowed, &candidates);
+///
+/// assert_eq!(vec![(&Word::new("fight"), &Word::new("might"))], pairs);
+/// ```
+pub fn indistinguishable_pairs<'a>(
+    allowed: &[Word],
+    candidates: &'a [Word],
+) -> Vec<(&'a Word, &'a Word)> {
+    let signatures = candidates
+        .iter()
+        .map(|candidate| {
+            allowed
+                .iter()
+                .map(|guess| Pattern::from_solution_and_guess(candidate, guess).hints)
+                .collect::<Vec<Vec<Hint>>>()
+        })
+        .collect::<Vec<_>>();
+    let mut pairs = Vec::new();
+
+    for (i, first) in candidates.iter().enumerate() {
+        for (j, second) in candidates.iter().enumerate().skip(i + 1) {
+            if signatures[i] == signatures[j] {
+                pairs.push((first, second));
+            }
+        }
+    }
+
+    pairs
+}
+
+/// Finds a fixed sequence of guesses which, once all played, tells every candidate apart.
+///
+/// Candidates are grouped by the patterns they produce for the guesses chosen so far. At each step,
+/// the allowed guess splitting these groups into the most groups is greedily picked, until every
+/// candidate is in its own group. This is an approximation: the sequence is not guaranteed to be
+/// the shortest one. If some candidates cannot be told apart (see [`indistinguishable_pairs`]), the
+/// sequence stops as soon as no guess splits the groups any further.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{greedy_decision_sequence, Word};
+/// let allowed = [Word::new("crane"), Word::new("light"), Word::new("fight")];
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("light")];
+/// let guesses = greedy_decision_sequence(&allowed, &candidates);
+///
+/// assert_eq!(vec![Word::new("light"), Word::new("fight")], guesses);
+/// ```
+pub fn greedy_decision_sequence(allowed: &[Word], candidates: &[Word]) -> Vec<Word> {
+    let mut groups = vec![candidates.iter().collect::<Vec<&Word>>()];
+    let mut guesses = Vec::new();
+
+    while groups.iter().any(|group| group.len() > 1) {
+        let best = allowed
+            .iter()
+            .map(|guess| (guess, split(&groups, guess)))
+            .min_by_key(|(_, split)| Reverse(split.len()));
+
+        match best {
+            Some((guess, split)) if split.len() > groups.len() => {
+                guesses.push(guess.clone());
+                groups = split;
+            }
+            _ => break,
+        }
+    }
+
+    guesses
+}
+
+/// Counts, for each letter and each position, how many candidates have that letter at that
+/// position. The first index is the letter, from `'a'` to `'z'`, and the second is the position,
+/// up to the length of the longest candidate. Letters of other alphabets are not counted.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{candidate_heatmap, Word};
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("flame")];
+/// let heatmap = candidate_heatmap(&candidates);
+///
+/// assert_eq!([2, 0, 0, 0, 0], heatmap[(b'f' - b'a') as usize][..]);
+/// assert_eq!([0, 0, 0, 0, 2], heatmap[(b't' - b'a') as usize][..]);
+/// ```
+pub fn candidate_heatmap<W: Borrow<Word>>(candidates: &[W]) -> [Vec<u32>; 26] {
+    let length = candidates
+        .iter()
+        .map(|candidate| candidate.borrow().length())
+        .max()
+        .unwrap_or(0);
+    let mut heatmap = std::array::from_fn(|_| vec![0; length]);
+
+    for candidate in candidates {
+        for (i, c) in candidate.borrow().indexed() {
+            if c.is_ascii_lowercase() {
+                heatmap[(c as u8 - b'a') as usize][i] += 1;
+            }
+        }
+    }
+
+    heatmap
+}
+
+/// Statistics on the words of a dictionary, e.g. to vet a custom one (see [`dictionary_stats`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DictStats {
+    /// The number of words, duplicates included.
+    pub total: usize,
+    /// The number of distinct words.
+    pub unique: usize,
+    /// The number of occurrences of each letter across the words, from `'a'` to `'z'`, a letter
+    /// appearing several times in a word being counted as many times. Letters of other alphabets
+    /// are not counted.
+    pub letter_counts: [usize; 26],
+    /// The number of words in which a letter appears more than once, such as `geese`.
+    pub repeated_letters: usize,
+}
+
+/// Computes statistics on the words of a dictionary: how many there are with and without
+/// duplicates, how often each letter appears, and how many words repeat a letter.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{dictionary_stats, Word};
+/// let words = [Word::new("geese"), Word::new("crane"), Word::new("geese")];
+/// let stats = dictionary_stats(&words);
+///
+/// assert_eq!(3, stats.total);
+/// assert_eq!(2, stats.unique);
+/// assert_eq!(7, stats.letter_counts[(b'e' - b'a') as usize]);
+/// assert_eq!(2, stats.repeated_letters);
+/// ```
+pub fn dictionary_stats(words: &[Word]) -> DictStats {
+    let mut letter_counts = [0; 26];
+
+    for word in words {
+        for c in word.chars().filter(char::is_ascii_lowercase) {
+            letter_counts[(c as u8 - b'a') as usize] += 1;
+        }
+    }
+
+    DictStats {
+        total: words.len(),
+        unique: words.iter().collect::<HashSet<&Word>>().len(),
+        letter_counts,
+        repeated_letters: words
+            .iter()
+            .filter(|word| (word.mask().count_ones() as usize) < word.length())
+            .count(),
+    }
+}
+
+fn split<'a>(groups: &[Vec<&'a Word>], guess: &Word) -> Vec<Vec<&'a Word>> {
+    let mut split = Vec::with_capacity(groups.len());
+
+    for group in groups {
+        let mut buckets: Vec<(Vec<Hint>, Vec<&Word>)> = Vec::new();
+
+        for candidate in group {
+            let hints = Pattern::from_solution_and_guess(candidate, guess).hints;
+
+            match buckets
+                .iter_mut()
+                .find(|(bucket_hints, _)| *bucket_hints == hints)
+            {
+                Some((_, bucket)) => bucket.push(candidate),
+                None => buckets.push((hints, vec![candidate])),
+            }
+        }
+
+        split.extend(buckets.into_iter().map(|(_, bucket)| bucket));
+    }
+
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::{
+        candidate_heatmap, dictionary_stats, greedy_decision_sequence, indistinguishable_pairs,
+    };
+    use crate::{Pattern, Word};
+
+    #[test]
+    fn given_twin_candidates_when_indistinguishable_pairs_then_return_twins() {
+        let allowed = [Word::new("crane"), Word::new("pouty")];
+        let candidates = [
+            Word::new("fight"),
+            Word::new("stare"),
+            Word::new("might"),
+            Word::new("light"),
+        ];
+        let pairs = indistinguishable_pairs(&allowed, &candidates);
+
+        assert_eq!(
+            vec![
+                (&Word::new("fight"), &Word::new("might")),
+                (&Word::new("fight"), &Word::new("light")),
+                (&Word::new("might"), &Word::new("light")),
+            ],
+            pairs
+        );
+    }
+
+    #[test]
+    fn given_candidates_are_allowed_guesses_when_indistinguishable_pairs_then_return_no_pairs() {
+        let candidates = [Word::new("fight"), Word::new("might"), Word::new("light")];
+        let pairs = indistinguishable_pairs(&candidates, &candidates);
+
+        assert!(pairs.is_empty());
+    }
+
+    #[test]
+    fn when_greedy_decision_sequence_then_every_candidate_has_its_own_patterns() {
+        let allowed = ["stare", "light", "plumb", "crane", "fight"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let candidates = ["fight", "might", "light", "sight", "tight"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let guesses = greedy_decision_sequence(&allowed, &candidates);
+
+        let signatures = candidates
+            .iter()
+            .map(|candidate| {
+                guesses
+                    .iter()
+                    .map(|guess| Pattern::from_solution_and_guess(candidate, guess).hints)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for (i, signature) in signatures.iter().enumerate() {
+            assert!(!signatures[i + 1..].contains(signature));
+        }
+    }
+
+    #[test]
+    fn given_twin_candidates_when_greedy_decision_sequence_then_stop_when_no_guess_splits() {
+        let allowed = [Word::new("crane"), Word::new("pouty")];
+        let candidates = [Word::new("fight"), Word::new("might"), Word::new("stare")];
+        let guesses = greedy_decision_sequence(&allowed, &candidates);
+
+        assert_eq!(vec![Word::new("crane")], guesses);
+    }
+
+    #[test]
+    fn given_single_candidate_when_greedy_decision_sequence_then_return_no_guesses() {
+        let guesses = greedy_decision_sequence(&[Word::new("crane")], &[Word::new("fight")]);
+
+        assert!(guesses.is_empty());
+    }
+
+    #[test]
+    fn when_candidate_heatmap_then_count_letters_at_each_position() {
+        let candidates = [Word::new("apple"), Word::new("ample"), Word::new("maple")];
+        let heatmap = candidate_heatmap(&candidates);
+        let row = |c: char| heatmap[(c as u8 - b'a') as usize].as_slice();
+
+        assert_eq!([2, 1, 0, 0, 0], row('a'));
+        assert_eq!([1, 1, 0, 0, 0], row('m'));
+        assert_eq!([0, 1, 3, 0, 0], row('p'));
+        assert_eq!([0, 0, 0, 3, 0], row('l'));
+        assert_eq!([0, 0, 0, 0, 3], row('e'));
+        assert_eq!(15, heatmap.iter().flatten().sum::<u32>());
+    }
+
+    #[test]
+    fn given_no_candidates_when_candidate_heatmap_then_every_row_is_empty() {
+        assert!(candidate_heatmap::<Word>(&[]).iter().all(Vec::is_empty));
+    }
+
+    #[test]
+    fn when_dictionary_stats_then_count_words_letters_and_repeated_letters() {
+        let words = ["apple", "crane", "APPLE", "llama", "fight"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let stats = dictionary_stats(&words);
+        let count = |c: char| stats.letter_counts[(c as u8 - b'a') as usize];
+
+        assert_eq!(5, stats.total);
+        assert_eq!(4, stats.unique);
+        assert_eq!(3, stats.repeated_letters);
+        assert_eq!(4, count('p'));
+        assert_eq!(4, count('l'));
+        assert_eq!(0, count('z'));
+        assert_eq!(25, stats.letter_counts.iter().sum::<usize>());
+    }
+
+    #[test]
+    fn given_no_words_when_dictionary_stats_then_every_count_is_zero() {
+        let stats = dictionary_stats(&[]);
+
+        assert_eq!(0, stats.total);
+        assert_eq!(0, stats.unique);
+        assert_eq!(0, stats.repeated_letters);
+        assert_eq!([0; 26], stats.letter_counts);
+    }
+}
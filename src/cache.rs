@@ -0,0 +1,133 @@
+use crate::pattern::{code, Pattern};
+use crate::word::Word;
+
+/// The number of characters of the longest words whose patterns fit in the `u8` codes of a
+/// [`PatternCache`], since 3⁵ = 243.
+const MAX_CACHED_LENGTH: usize = 5;
+
+/// The pattern of every guess against every solution of a list of words, computed once so that
+/// scoring guesses over the same words many times only looks patterns up.
+///
+/// Patterns are stored as codes, like [`Pattern::to_code`] makes them, in an `N×N` table for `N`
+/// words, which takes `N²` bytes: about 168 MB for the 12,974 words of the default list.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Pattern, PatternCache, Word};
+/// let words = [Word::new("stunt"), Word::new("attic"), Word::new("crane")];
+/// let cache = PatternCache::build(&words);
+///
+/// assert_eq!(3, cache.len());
+/// assert_eq!(
+///     Pattern::from_solution_and_guess(&words[0], &words[1]).to_code(),
+///     cache.pattern(1, 0) as u16
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct PatternCache {
+    codes: Vec<u8>,
+    len: usize,
+}
+
+impl PatternCache {
+    /// Computes the pattern of every word as a guess against every word as the solution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the words are not all as long, or if they have more than 5 characters.
+    pub fn build(words: &[Word]) -> Self {
+        if let Some(first) = words.first() {
+            assert!(
+                first.length() <= MAX_CACHED_LENGTH,
+                "pattern caches only support words of at most {} characters",
+                MAX_CACHED_LENGTH
+            );
+            assert!(
+                words.iter().all(|word| word.length() == first.length()),
+                "pattern caches only support words of the same length"
+            );
+        }
+
+        let codes = words
+            .iter()
+            .flat_map(|guess| {
+                words.iter().map(move |solution| {
+                    code(&Pattern::from_solution_and_guess(solution, guess).hints) as u8
+                })
+            })
+            .collect();
+
+        PatternCache {
+            codes,
+            len: words.len(),
+        }
+    }
+
+    /// Returns the code of the pattern of the guess against the solution, both given by their
+    /// index in the words the cache was built from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is not lower than the number of words.
+    pub fn pattern(&self, guess: usize, solution: usize) -> u8 {
+        assert!(
+            guess < self.len && solution < self.len,
+            "index out of bounds for a cache of {} words",
+            self.len
+        );
+
+        self.codes[guess * self.len + solution]
+    }
+
+    /// Returns the number of words the cache was built from.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the cache was built from no word.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::PatternCache;
+    use crate::{Pattern, Word};
+
+    #[test]
+    fn when_build_then_every_pattern_is_the_computed_one() {
+        let words = ["geese", "beret", "eerie", "crane", "fight"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let cache = PatternCache::build(&words);
+
+        for (i, guess) in words.iter().enumerate() {
+            for (j, solution) in words.iter().enumerate() {
+                assert_eq!(
+                    Pattern::from_solution_and_guess(solution, guess).to_code(),
+                    cache.pattern(i, j) as u16
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn given_no_words_when_build_then_cache_is_empty() {
+        assert!(PatternCache::build(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn given_longer_words_when_build_then_panic() {
+        PatternCache::build(&[Word::with_length("plumbs", 6).unwrap()]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn given_index_out_of_bounds_when_pattern_then_panic() {
+        PatternCache::build(&[Word::new("crane")]).pattern(0, 1);
+    }
+}
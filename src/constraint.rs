@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::pattern::Hint::{Black, Green, Yellow};
 use crate::pattern::Pattern;
@@ -7,10 +10,18 @@ use crate::word::Word;
 /// A set of constraints for which words may be matched against.
 pub struct Constraints {
     constraints: Vec<Constraint>,
+    length: usize,
+    absent: u32,
 }
 
 impl Constraints {
-    /// Constructs constraints from a pattern.
+    /// Constructs constraints from a pattern. Only words as long as the guess of the pattern may
+    /// match the constraints.
+    ///
+    /// When a letter is guessed more times than it appears in the solution, its extra copies are
+    /// black while the others are green or yellow. The letter then appears exactly as many times as
+    /// it is green or yellow, e.g. guessing `geese` against `beret` gives `bgybb`, so the word has
+    /// exactly two e's, one of them at the second position, and none at the third or fifth.
     ///
     /// # Examples
     ///
@@ -21,16 +32,28 @@ impl Constraints {
     ///
     /// assert!(constraints.matches(&Word::new("spade")));
     /// assert!(!constraints.matches(&Word::new("forgo")));
+    ///
+    /// let pattern = Pattern::from_solution_and_guess(&Word::new("beret"), &Word::new("geese"));
+    /// let constraints = Constraints::from_pattern(&pattern);
+    ///
+    /// assert!(constraints.matches(&Word::new("meter")));
+    /// assert!(!constraints.matches(&Word::new("berth")));
+    /// assert!(!constraints.matches(&Word::new("tepee")));
     /// ```
     pub fn from_pattern(pattern: &Pattern) -> Self {
         let Pattern { guess, hints } = pattern;
+
+        if hints.iter().all(|hint| hint.is_black()) {
+            return Constraints::absent(guess);
+        }
+
         let mut constraints = Vec::new();
-        let mut hints_by_char = HashMap::with_capacity(Word::SIZE);
+        let mut hints_by_char = BTreeMap::new();
 
-        for (i, (c, hint)) in guess.chars().zip(hints).enumerate() {
+        for ((i, c), hint) in guess.indexed().zip(hints) {
             hints_by_char
                 .entry(c)
-                .or_insert_with(|| Vec::with_capacity(Word::SIZE))
+                .or_insert_with(|| Vec::with_capacity(guess.length()))
                 .push((i, hint));
         }
 
@@ -42,26 +65,20 @@ impl Constraints {
                 });
             }
 
-            let yellow_count = hints
-                .iter()
-                .filter(|(_, hint)| matches!(hint, Yellow))
-                .count();
-            let black_count = hints
-                .iter()
-                .filter(|(_, hint)| matches!(hint, Black))
-                .count();
+            let yellow_count = hints.iter().filter(|(_, hint)| hint.is_yellow()).count();
+            let black_count = hints.iter().filter(|(_, hint)| hint.is_black()).count();
 
             if yellow_count > 0 || black_count > 0 {
                 let green_positions = hints
                     .iter()
-                    .filter(|(_, hint)| matches!(hint, Green))
+                    .filter(|(_, hint)| hint.is_green())
                     .map(|(i, _)| *i)
                     .collect::<Vec<usize>>();
 
                 if yellow_count > 0 {
                     let at_least = Constraint::at_least(
                         yellow_count,
-                        Constraint::not_at(&green_positions),
+                        Constraint::not_at(&green_positions, guess.length()),
                         char,
                     );
                     constraints.push(at_least);
@@ -70,7 +87,7 @@ impl Constraints {
                 if black_count > 0 {
                     let at_most = Constraint::at_most(
                         yellow_count,
-                        Constraint::not_at(&green_positions),
+                        Constraint::not_at(&green_positions, guess.length()),
                         char,
                     );
                     constraints.push(at_most);
@@ -78,23 +95,548 @@ impl Constraints {
             }
         }
 
-        Constraints { constraints }
+        Constraints::with_length(constraints, guess.length())
+    }
+
+    /// Constructs the constraints of an all-black pattern, which only forbid every letter of the
+    /// guess anywhere in the word. This is the most common pattern early in a game.
+    fn absent(guess: &Word) -> Self {
+        let constraints = guess
+            .chars()
+            .collect::<BTreeSet<char>>()
+            .into_iter()
+            .map(|c| Constraint::at_most(0, (0..guess.length()).collect(), c))
+            .collect();
+
+        Constraints::with_length(constraints, guess.length())
+    }
+
+    /// Combines constraints built by hand, which allow a word of [`Word::SIZE`] characters only
+    /// when every one of them does, e.g. to filter words beyond what hints tell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Constraint, Constraints, Word};
+    /// // Contains both 's' and 't', but no doubled letter.
+    /// let mut constraints = vec![Constraint::contains('s'), Constraint::contains('t')];
+    /// constraints.extend(('a'..='z').map(|c| Constraint::at_most(1, vec![0, 1, 2, 3, 4], c)));
+    /// let constraints = Constraints::new(constraints);
+    ///
+    /// assert!(constraints.matches(&Word::new("stare")));
+    /// assert!(!constraints.matches(&Word::new("state")));
+    /// assert!(!constraints.matches(&Word::new("crane")));
+    /// ```
+    pub fn new(constraints: Vec<Constraint>) -> Self {
+        Constraints::with_length(constraints, Word::SIZE)
+    }
+
+    /// Combines constraints built by hand like [`Constraints::new`], for words of `length`
+    /// characters. Positions past the end of such words are dropped, since they count no letter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Constraint, Constraints, Word};
+    /// let constraints = Constraints::with_length(vec![Constraint::at_most(0, vec![0, 1, 2, 3, 9], 'e')], 5);
+    ///
+    /// assert!(constraints.matches(&Word::new("crane")));
+    /// assert!(!constraints.matches(&Word::new("beret")));
+    /// ```
+    pub fn with_length(constraints: Vec<Constraint>, length: usize) -> Self {
+        let constraints = constraints
+            .into_iter()
+            .map(|constraint| constraint.within(length))
+            .collect::<Vec<Constraint>>();
+        // Letters forbidden anywhere in the word are kept as a mask, so that they are checked at
+        // once when matching.
+        let absent = constraints
+            .iter()
+            .filter(|constraint| constraint.is_absent(length))
+            .fold(0, |mask, constraint| {
+                mask | 1 << (*constraint.char() as u8 - b'a')
+            });
+
+        Constraints {
+            constraints,
+            length,
+            absent,
+        }
+    }
+
+    /// Combines several constraints into one, which allows a word only when every one of them
+    /// does, so that the constraints of many patterns are checked at once. Constraints shared by
+    /// several patterns are only kept once. Merging no constraints gives constraints which allow
+    /// every word of [`Word::SIZE`] characters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constraints are not all for words of the same length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Constraints, Pattern, Word};
+    /// let solution = Word::new("fight");
+    /// let merged = Constraints::merge(&[
+    ///     Constraints::from_pattern(&Pattern::from_solution_and_guess(&solution, &Word::new("crane"))),
+    ///     Constraints::from_pattern(&Pattern::from_solution_and_guess(&solution, &Word::new("light"))),
+    /// ]);
+    ///
+    /// assert!(merged.matches(&Word::new("might")));
+    /// assert!(!merged.matches(&Word::new("night")));
+    /// assert!(!merged.matches(&Word::new("bulky")));
+    /// ```
+    pub fn merge(others: &[Constraints]) -> Self {
+        let length = others.first().map_or(Word::SIZE, |other| other.length);
+        assert!(
+            others.iter().all(|other| other.length == length),
+            "cannot merge constraints for words of different lengths"
+        );
+
+        let mut constraints: Vec<Constraint> = Vec::new();
+
+        for constraint in others.iter().flat_map(|other| &other.constraints) {
+            if !constraints.contains(constraint) {
+                constraints.push(constraint.clone());
+            }
+        }
+
+        Constraints::with_length(constraints, length)
     }
 
     /// Matches a word against the constraints, returning whether the constraints allow the word.
     pub fn matches(&self, word: &Word) -> bool {
+        // The bits of the mask of absent letters are those of letters from a to z, which other
+        // alphabets may give to their own letters.
+        let masked = word.is_ascii();
+
+        word.length() == self.length
+            && (!masked || word.mask() & self.absent == 0)
+            && self.constraints.iter().all(|constraint| {
+                (masked && constraint.is_absent(self.length)) || constraint.matches(word)
+            })
+    }
+
+    /// Describes each constraint in plain English, e.g. to explain why a word does not match.
+    /// Positions start at 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Constraints, Pattern, Word};
+    /// let pattern = Pattern::from_solution_and_guess(&Word::new("fight"), &Word::new("tiger"));
+    /// let descriptions = Constraints::from_pattern(&pattern).describe();
+    ///
+    /// assert!(descriptions.contains(&"position 1 must be 'i'".to_string()));
+    /// assert!(descriptions.contains(&"position 0 must not be 't'".to_string()));
+    /// assert!(descriptions.contains(&"letter 't' appears at least 1 time".to_string()));
+    /// assert!(descriptions.contains(&"letter 'r' is absent".to_string()));
+    /// ```
+    pub fn describe(&self) -> Vec<String> {
+        self.constraints
+            .iter()
+            .map(|constraint| constraint.describe(self.length))
+            .collect()
+    }
+
+    /// Finds two constraints which no word can satisfy together, e.g. a position which must be
+    /// two different letters, or a letter which must both appear and be absent. Returns their
+    /// descriptions, as given by [`Constraints::describe`].
+    ///
+    /// Constraints without such a pair may still allow no word, since only pairs are compared.
+    pub(crate) fn contradiction(&self) -> Option<(String, String)> {
+        for first in &self.constraints {
+            let Constraint::AtLeast {
+                positions,
+                count,
+                char,
+            } = first
+            else {
+                continue;
+            };
+
+            let upper_bound = self.constraints.iter().find(|second| match second {
+                Constraint::AtMost {
+                    positions: other_positions,
+                    count: other_count,
+                    char: other_char,
+                } => {
+                    other_char == char
+                        && other_count < count
+                        && positions.iter().all(|i| other_positions.contains(i))
+                }
+                Constraint::AtLeast { .. } => false,
+            });
+            let taken = self
+                .constraints
+                .iter()
+                .filter(|second| match second {
+                    Constraint::AtLeast {
+                        positions: other_positions,
+                        count: 1,
+                        char: other_char,
+                    } => {
+                        other_char != char
+                            && other_positions.len() == 1
+                            && positions.contains(&other_positions[0])
+                    }
+                    _ => false,
+                })
+                .collect::<Vec<&Constraint>>();
+            let second = match upper_bound {
+                Some(second) => second,
+                None if positions.len() < count + taken.len() => taken[0],
+                None => continue,
+            };
+
+            return Some((first.describe(self.length), second.describe(self.length)));
+        }
+
+        None
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Constraint> {
+        self.constraints.iter()
+    }
+
+    /// Counts how many of the constraints the word does not satisfy. A word which is not as long
+    /// as the constraints require satisfies none of them.
+    pub fn violations(&self, word: &Word) -> usize {
+        if word.length() != self.length {
+            return self.constraints.len();
+        }
+
+        self.constraints
+            .iter()
+            .filter(|constraint| !constraint.matches(word))
+            .count()
+    }
+
+    /// Tells how the word violates each of the constraints it does not satisfy, e.g. "missing
+    /// required 'e'" or "has forbidden 't' at position 2", in the order of
+    /// [`Constraints::describe`]. Positions start at 0. Returns no reason if the word matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Constraints, Pattern, Word};
+    /// let pattern = Pattern::from_solution_and_guess(&Word::new("fight"), &Word::new("tiger"));
+    /// let constraints = Constraints::from_pattern(&pattern);
+    ///
+    /// assert_eq!(
+    ///     vec!["has forbidden 't' at position 0".to_string()],
+    ///     constraints.reasons(&Word::new("tight"))
+    /// );
+    /// assert!(constraints.reasons(&Word::new("fight")).is_empty());
+    /// ```
+    pub fn reasons(&self, word: &Word) -> Vec<String> {
+        if word.length() != self.length {
+            return vec![format!("is not {}-character long", self.length)];
+        }
+
         self.constraints
             .iter()
-            .all(|constraint| constraint.matches(word))
+            .filter(|constraint| !constraint.matches(word))
+            .map(|constraint| constraint.reason(self.length))
+            .collect()
+    }
+
+    /// Returns whether every word matching these constraints also matches the other constraints,
+    /// i.e. whether these constraints are at least as tight as the other ones.
+    ///
+    /// The check is conservative: it may return `false` even though the constraints imply the
+    /// other ones, but never returns `true` when they do not. It always holds when these
+    /// constraints contain every constraint of the other ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Constraints, Pattern, Word};
+    /// let solution = Word::new("stare");
+    /// let solved = Constraints::from_pattern(&solution.self_pattern());
+    /// let start = Constraints::from_pattern(&Pattern::from_solution_and_guess(&solution, &Word::new("start")));
+    ///
+    /// assert!(solved.implies(&start));
+    /// assert!(!start.implies(&solved));
+    /// ```
+    pub fn implies(&self, other: &Constraints) -> bool {
+        let locked = self.locked_chars();
+
+        self.length == other.length
+            && other
+                .constraints
+                .iter()
+                .all(|constraint| self.implies_constraint(constraint, &locked))
+    }
+
+    fn implies_constraint(&self, other: &Constraint, locked: &[Option<char>]) -> bool {
+        match other {
+            Constraint::AtLeast {
+                positions,
+                count,
+                char,
+            } => {
+                let locked_count = positions
+                    .iter()
+                    .filter(|&&i| locked[i] == Some(*char))
+                    .count();
+
+                locked_count >= *count
+                    || self.constraints.iter().any(|constraint| match constraint {
+                        Constraint::AtLeast {
+                            positions: own_positions,
+                            count: own_count,
+                            char: own_char,
+                        } => {
+                            own_char == char
+                                && own_count >= count
+                                && own_positions.iter().all(|i| positions.contains(i))
+                        }
+                        Constraint::AtMost { .. } => false,
+                    })
+            }
+            Constraint::AtMost {
+                positions,
+                count,
+                char,
+            } => {
+                let free_positions = positions
+                    .iter()
+                    .filter(|&&i| locked[i].is_none_or(|c| c == *char))
+                    .collect::<Vec<&usize>>();
+
+                free_positions.len() <= *count
+                    || self.constraints.iter().any(|constraint| match constraint {
+                        Constraint::AtMost {
+                            positions: own_positions,
+                            count: own_count,
+                            char: own_char,
+                        } => {
+                            own_char == char
+                                && own_count <= count
+                                && free_positions.iter().all(|i| own_positions.contains(i))
+                        }
+                        Constraint::AtLeast { .. } => false,
+                    })
+            }
+        }
+    }
+
+    /// Exports the constraints in the JSON format shared by many Wordle solvers, made of the
+    /// letters at their correct position, the letters present but not at a position, and the
+    /// letters absent from the word, e.g.
+    /// `{"correct":{"0":"c"},"present":[["r",2]],"absent":["s","t"]}`.
+    ///
+    /// The format cannot tell how many times a letter appears, so constraints on repeated letters
+    /// are loosened: a present letter is only known to appear once more than its correct
+    /// positions, and a letter is absent when it appears nowhere but at its correct positions.
+    ///
+    /// Requires the `serde` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Constraints, Pattern, Word};
+    /// let pattern = Pattern::from_solution_and_guess(&Word::new("cigar"), &Word::new("crust"));
+    /// let constraints = Constraints::from_pattern(&pattern);
+    ///
+    /// assert_eq!(
+    ///     r#"{"correct":{"0":"c"},"present":[["r",1]],"absent":["s","t","u"]}"#,
+    ///     constraints.to_solver_json()
+    /// );
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_solver_json(&self) -> String {
+        let locked = self.locked_chars();
+        let mut present = BTreeSet::new();
+        let mut misplaced = BTreeSet::new();
+        let mut absent = BTreeSet::new();
+
+        for constraint in &self.constraints {
+            if let Constraint::AtLeast {
+                positions,
+                count,
+                char,
+            } = constraint
+            {
+                if *count > 0 && positions.iter().any(|&i| locked[i] != Some(*char)) {
+                    present.insert(*char);
+                }
+            }
+        }
+
+        for constraint in &self.constraints {
+            if let Constraint::AtMost {
+                positions,
+                count: 0,
+                char,
+            } = constraint
+            {
+                if present.contains(char) && positions.len() == 1 {
+                    misplaced.insert((*char, positions[0]));
+                } else if !present.contains(char)
+                    && (0..self.length).all(|i| locked[i] == Some(*char) || positions.contains(&i))
+                {
+                    absent.insert(*char);
+                }
+            }
+        }
+
+        let json = SolverJson {
+            correct: locked
+                .iter()
+                .enumerate()
+                .filter_map(|(i, c)| c.map(|c| (i, c)))
+                .collect(),
+            present: misplaced.into_iter().collect(),
+            absent,
+        };
+
+        // Positions and letters are always valid JSON, so serializing cannot fail.
+        serde_json::to_string(&json).unwrap()
+    }
+
+    /// Imports constraints on words of [`Word::SIZE`] characters from the JSON format described in
+    /// [`Constraints::to_solver_json`]. Missing fields are considered empty.
+    ///
+    /// Returns an error if the JSON does not follow the format, if a position is out of the word,
+    /// or if a letter is both present and absent. Requires the `serde` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Constraints, Word};
+    /// let json = r#"{"correct": {"0": "c"}, "present": [["r", 1]], "absent": ["s", "t", "u"]}"#;
+    /// let constraints = Constraints::from_solver_json(json).unwrap();
+    ///
+    /// assert!(constraints.matches(&Word::new("cigar")));
+    /// assert!(!constraints.matches(&Word::new("crane")));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_solver_json(json: &str) -> Result<Self, String> {
+        let json: SolverJson = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        let correct = json
+            .correct
+            .into_iter()
+            .map(|(i, c)| Ok((position_in_word(i)?, solver_letter(c)?)))
+            .collect::<Result<Vec<(usize, char)>, String>>()?;
+        let misplaced = json
+            .present
+            .into_iter()
+            .map(|(c, i)| Ok((solver_letter(c)?, position_in_word(i)?)))
+            .collect::<Result<Vec<(char, usize)>, String>>()?;
+        let absent = json
+            .absent
+            .into_iter()
+            .map(solver_letter)
+            .collect::<Result<Vec<char>, String>>()?;
+
+        let correct_positions = |char: char| {
+            correct
+                .iter()
+                .filter(|(_, c)| *c == char)
+                .map(|(i, _)| *i)
+                .collect::<Vec<usize>>()
+        };
+        let present = misplaced.iter().map(|(c, _)| *c).collect::<BTreeSet<_>>();
+        let mut constraints = Vec::new();
+
+        for &(i, char) in &correct {
+            constraints.push(Constraint::lock(i, char));
+        }
+
+        for &(char, i) in &misplaced {
+            constraints.push(Constraint::forbid(i, char));
+        }
+
+        for &char in &present {
+            let positions = Constraint::not_at(&correct_positions(char), Word::SIZE);
+            constraints.push(Constraint::at_least(1, positions, char));
+        }
+
+        for &char in &absent {
+            if present.contains(&char) {
+                return Err(format!("letter {} is both present and absent", char));
+            }
+
+            let positions = Constraint::not_at(&correct_positions(char), Word::SIZE);
+            constraints.push(Constraint::at_most(0, positions, char));
+        }
+
+        Ok(Constraints::new(constraints))
+    }
+
+    pub(crate) fn locked_chars(&self) -> Vec<Option<char>> {
+        let mut locked = vec![None; self.length];
+
+        for constraint in &self.constraints {
+            if let Constraint::AtLeast {
+                positions,
+                count,
+                char,
+            } = constraint
+            {
+                if *count > 0 && *count == positions.len() {
+                    for &i in positions {
+                        locked[i] = Some(*char);
+                    }
+                }
+            }
+        }
+
+        locked
+    }
+}
+
+#[cfg(feature = "serde")]
+fn position_in_word(position: usize) -> Result<usize, String> {
+    if position < Word::SIZE {
+        Ok(position)
+    } else {
+        Err(format!("position {} is out of the word", position))
+    }
+}
+
+#[cfg(feature = "serde")]
+fn solver_letter(c: char) -> Result<char, String> {
+    match c.to_lowercase().collect::<Vec<char>>()[..] {
+        [lowercase] if lowercase.is_alphabetic() => Ok(lowercase),
+        _ => Err(format!("{} is not a letter", c)),
     }
 }
 
-enum Constraint {
+/// The format of [`Constraints::to_solver_json`], where missing fields are empty.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SolverJson {
+    #[serde(default)]
+    correct: BTreeMap<usize, char>,
+    #[serde(default)]
+    present: Vec<(char, usize)>,
+    #[serde(default)]
+    absent: BTreeSet<char>,
+}
+
+/// A constraint on how many times a letter appears among some positions of a word, positions
+/// starting at 0. Every hint comes down to such constraints, see [`Constraints::from_pattern`].
+///
+/// Constraints are built with the constructors below, which are the supported way of making them,
+/// then combined with [`Constraints::new`]. Letters are lowercased, positions given several times
+/// are only counted once, and constraints on positions past the end of a word count no letter
+/// there.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Constraint {
+    /// The letter appears at least `count` times among the positions.
+    #[non_exhaustive]
     AtLeast {
         positions: Vec<usize>,
         count: usize,
         char: char,
     },
+    /// The letter appears at most `count` times among the positions.
+    #[non_exhaustive]
     AtMost {
         positions: Vec<usize>,
         count: usize,
@@ -103,40 +645,76 @@ enum Constraint {
 }
 
 impl Constraint {
-    fn lock(position: usize, char: char) -> Self {
-        Constraint::AtLeast {
-            positions: vec![position],
-            count: 1,
-            char,
-        }
+    /// Requires the letter at the position, like a green hint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the character is not a letter, in any case. This holds for every constructor.
+    /// Letters other than `a` to `z` are for words of other alphabets (see
+    /// [`Word::with_alphabet`]).
+    pub fn lock(position: usize, char: char) -> Self {
+        Constraint::at_least(1, vec![position], char)
     }
 
-    fn forbid(position: usize, char: char) -> Self {
-        Constraint::AtMost {
-            positions: vec![position],
-            count: 0,
-            char,
-        }
+    /// Forbids the letter at the position, like a yellow hint does for its own position.
+    pub fn forbid(position: usize, char: char) -> Self {
+        Constraint::at_most(0, vec![position], char)
+    }
+
+    /// Requires the letter somewhere in a word of [`Word::SIZE`] characters.
+    pub fn contains(char: char) -> Self {
+        Constraint::at_least(1, (0..Word::SIZE).collect(), char)
+    }
+
+    /// Forbids the letter anywhere in a word of [`Word::SIZE`] characters.
+    pub fn absent(char: char) -> Self {
+        Constraint::at_most(0, (0..Word::SIZE).collect(), char)
     }
 
-    fn at_least(count: usize, positions: Vec<usize>, char: char) -> Self {
+    /// Requires the letter at least `count` times among the positions.
+    pub fn at_least(count: usize, positions: Vec<usize>, char: char) -> Self {
         Constraint::AtLeast {
-            positions,
+            positions: Constraint::distinct(positions),
             count,
-            char,
+            char: Constraint::letter(char),
         }
     }
 
-    fn at_most(count: usize, positions: Vec<usize>, char: char) -> Self {
+    /// Allows the letter at most `count` times among the positions.
+    pub fn at_most(count: usize, positions: Vec<usize>, char: char) -> Self {
         Constraint::AtMost {
-            positions,
+            positions: Constraint::distinct(positions),
             count,
-            char,
+            char: Constraint::letter(char),
+        }
+    }
+
+    fn letter(char: char) -> char {
+        match char.to_lowercase().collect::<Vec<char>>()[..] {
+            [lowercase] if lowercase.is_alphabetic() => lowercase,
+            _ => panic!("'{}' is not a letter", char),
+        }
+    }
+
+    fn distinct(mut positions: Vec<usize>) -> Vec<usize> {
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
+
+    /// Drops the positions which are not within words of `length` characters.
+    fn within(mut self, length: usize) -> Self {
+        match &mut self {
+            Constraint::AtLeast { positions, .. } | Constraint::AtMost { positions, .. } => {
+                positions.retain(|&i| i < length)
+            }
         }
+
+        self
     }
 
-    fn not_at(positions: &[usize]) -> Vec<usize> {
-        (0..Word::SIZE).filter(|i| !positions.contains(i)).collect()
+    fn not_at(positions: &[usize], length: usize) -> Vec<usize> {
+        (0..length).filter(|i| !positions.contains(i)).collect()
     }
 
     fn positions(&self) -> &[usize] {
@@ -153,13 +731,115 @@ impl Constraint {
         }
     }
 
+    /// Returns whether the constraint forbids its character anywhere in the word, in which case it
+    /// is checked by the mask of absent letters of [`Constraints`] for words made of letters from
+    /// `a` to `z`. Only such letters have a known bit in the mask.
+    fn is_absent(&self, length: usize) -> bool {
+        matches!(
+            self,
+            Constraint::AtMost { positions, count: 0, char }
+                if char.is_ascii_lowercase() && (0..length).all(|i| positions.contains(&i))
+        )
+    }
+
+    /// Lists the positions the constraint does not count, such as ` (not at positions 1,4)`, or
+    /// nothing if it counts every position.
+    fn outside(&self, length: usize) -> String {
+        let excluded = Constraint::not_at(self.positions(), length)
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<String>>();
+
+        if excluded.is_empty() {
+            String::new()
+        } else {
+            format!(" (not at positions {})", excluded.join(","))
+        }
+    }
+
+    fn describe(&self, length: usize) -> String {
+        let times = |count: usize| if count == 1 { "time" } else { "times" };
+        let outside = self.outside(length);
+
+        match self {
+            Constraint::AtLeast {
+                positions,
+                count: 1,
+                char,
+            } if positions.len() == 1 => format!("position {} must be '{}'", positions[0], char),
+            Constraint::AtMost {
+                positions,
+                count: 0,
+                char,
+            } if positions.len() == 1 && length > 1 => {
+                format!("position {} must not be '{}'", positions[0], char)
+            }
+            Constraint::AtMost {
+                positions,
+                count: 0,
+                char,
+            } if positions.len() == length => format!("letter '{}' is absent", char),
+            Constraint::AtLeast { count, char, .. } => format!(
+                "letter '{}' appears at least {} {}{}",
+                char,
+                count,
+                times(*count),
+                outside
+            ),
+            Constraint::AtMost { count, char, .. } => format!(
+                "letter '{}' appears at most {} {}{}",
+                char,
+                count,
+                times(*count),
+                outside
+            ),
+        }
+    }
+
+    /// Tells how a word violates the constraint, like [`Constraint::describe`] tells what the
+    /// constraint requires.
+    fn reason(&self, length: usize) -> String {
+        let outside = self.outside(length);
+
+        match self {
+            Constraint::AtLeast {
+                positions,
+                count: 1,
+                char,
+            } if positions.len() == 1 => format!("missing '{}' at position {}", char, positions[0]),
+            Constraint::AtMost {
+                positions,
+                count: 0,
+                char,
+            } if positions.len() == 1 && length > 1 => {
+                format!("has forbidden '{}' at position {}", char, positions[0])
+            }
+            Constraint::AtMost {
+                positions,
+                count: 0,
+                char,
+            } if positions.len() == length => format!("has absent letter '{}'", char),
+            Constraint::AtLeast { count: 1, char, .. } => {
+                format!("missing required '{}'{}", char, outside)
+            }
+            Constraint::AtLeast { count, char, .. } => {
+                format!("has fewer than {} '{}'{}", count, char, outside)
+            }
+            Constraint::AtMost { count, char, .. } => {
+                format!("has more than {} '{}'{}", count, char, outside)
+            }
+        }
+    }
+
     fn matches(&self, word: &Word) -> bool {
-        let char_count = word
-            .chars()
-            .enumerate()
-            .filter(|(i, _)| self.positions().contains(i))
-            .filter(|(_, c)| c == self.char())
-            .count();
+        let letters = word.letters();
+        // A letter which is not from a to z has no known index unless the word has it.
+        let char_count = word.letter(*self.char()).map_or(0, |letter| {
+            self.positions()
+                .iter()
+                .filter(|&&i| letters.get(i) == Some(&letter))
+                .count()
+        });
 
         match self {
             Constraint::AtLeast { count, .. } => char_count >= *count,
@@ -170,12 +850,20 @@ impl Constraint {
 
 #[cfg(test)]
 mod tests {
-    use crate::constraint::Constraints;
-    use crate::{Pattern, Word};
+    use crate::constraint::{Constraint, Constraints};
+    use crate::pattern::Hint::{Black, Green, Yellow};
+    use crate::{load_default_words, Alphabet, Pattern, Word};
+
+    fn constraints(solution: &str, guess: &str) -> Constraints {
+        Constraints::from_pattern(&Pattern::from_solution_and_guess(
+            &Word::new(solution),
+            &Word::new(guess),
+        ))
+    }
 
     #[test]
     fn given_guess_is_solution_when_matches_then_pattern_matches_solution() {
-        let pattern = Pattern::from_solution_and_guess(&Word::new("stare"), &Word::new("stare"));
+        let pattern = Word::new("stare").self_pattern();
         let constraints = Constraints::from_pattern(&pattern);
 
         assert!(constraints.matches(&Word::new("stare")));
@@ -183,7 +871,7 @@ mod tests {
 
     #[test]
     fn given_guess_is_solution_when_matches_then_pattern_does_not_match_non_solution_words() {
-        let pattern = Pattern::from_solution_and_guess(&Word::new("stare"), &Word::new("stare"));
+        let pattern = Word::new("stare").self_pattern();
         let constraints = Constraints::from_pattern(&pattern);
 
         assert!(!constraints.matches(&Word::new("start")));
@@ -294,6 +982,33 @@ mod tests {
         assert!(!constraints.matches(&Word::new("tibia")));
     }
 
+    #[test]
+    fn given_guess_contains_green_yellow_and_black_for_the_same_letter_when_matches_then_words_with_exactly_the_revealed_count_match(
+    ) {
+        let pattern = Pattern::from_solution_and_guess(&Word::new("beret"), &Word::new("geese"));
+        let constraints = Constraints::from_pattern(&pattern);
+
+        assert_eq!([Black, Green, Yellow, Black, Black], pattern.hints[..]);
+        assert!(constraints.matches(&Word::new("beret")));
+        assert!(constraints.matches(&Word::new("meter")));
+        assert!(constraints.matches(&Word::new("fever")));
+        assert!(!constraints.matches(&Word::new("berth")));
+        assert!(!constraints.matches(&Word::new("eeven")));
+    }
+
+    #[test]
+    fn given_guess_contains_a_letter_twice_and_solution_once_when_matches_then_words_with_the_letter_twice_do_not_match(
+    ) {
+        let pattern = Pattern::from_solution_and_guess(&Word::new("abide"), &Word::new("speed"));
+        let constraints = Constraints::from_pattern(&pattern);
+
+        assert_eq!([Black, Black, Yellow, Black, Yellow], pattern.hints[..]);
+        assert!(constraints.matches(&Word::new("abide")));
+        assert!(constraints.matches(&Word::new("diode")));
+        assert!(!constraints.matches(&Word::new("elude")));
+        assert!(!constraints.matches(&Word::new("dingo")));
+    }
+
     #[test]
     fn given_guess_contains_yellows_and_blacks_for_the_same_letter_when_matches_then_words_with_greater_occurrences_of_yellow_do_not_match(
     ) {
@@ -306,4 +1021,367 @@ mod tests {
         assert!(!constraints.matches(&Word::new("rodeo")));
         assert!(!constraints.matches(&Word::new("motto")));
     }
+
+    #[test]
+    fn when_violations_then_count_unsatisfied_constraints() {
+        let constraints = constraints("stare", "stare");
+
+        assert_eq!(0, constraints.violations(&Word::new("stare")));
+        assert_eq!(1, constraints.violations(&Word::new("store")));
+        assert_eq!(5, constraints.violations(&Word::new("lucky")));
+    }
+
+    #[test]
+    fn when_implies_then_constraints_imply_themselves() {
+        let constraints = constraints("tonal", "swoop");
+
+        assert!(constraints.implies(&constraints));
+    }
+
+    #[test]
+    fn given_constraints_contain_the_other_constraints_when_implies_then_return_true() {
+        let mut combined = constraints("larva", "stare");
+        combined
+            .constraints
+            .extend(constraints("larva", "radar").constraints);
+
+        assert!(combined.implies(&constraints("larva", "stare")));
+        assert!(combined.implies(&constraints("larva", "radar")));
+        assert!(!constraints("larva", "stare").implies(&combined));
+    }
+
+    #[test]
+    fn given_greens_lock_every_position_when_implies_then_looser_constraints_are_implied() {
+        let solved = constraints("stare", "stare");
+
+        assert!(solved.implies(&constraints("stare", "start")));
+        assert!(solved.implies(&constraints("stare", "tears")));
+        assert!(!constraints("stare", "start").implies(&solved));
+    }
+
+    #[test]
+    fn given_unrelated_constraints_when_implies_then_return_false() {
+        assert!(!constraints("toner", "poser").implies(&constraints("toner", "watch")));
+        assert!(!constraints("toner", "watch").implies(&constraints("toner", "poser")));
+    }
+
+    #[test]
+    fn given_all_black_pattern_when_from_pattern_then_only_forbid_guessed_letters_entirely() {
+        let constraints = constraints("watch", "geese");
+        let chars = constraints
+            .iter()
+            .map(|constraint| match constraint {
+                Constraint::AtMost {
+                    positions,
+                    count: 0,
+                    char,
+                } if positions.len() == Word::SIZE => *char,
+                _ => panic!("constraint does not forbid a letter entirely"),
+            })
+            .collect::<Vec<char>>();
+
+        assert_eq!(vec!['e', 'g', 's'], chars);
+    }
+
+    #[test]
+    fn given_all_black_pattern_when_matches_then_words_containing_any_guessed_letter_do_not_match()
+    {
+        let guess = Word::new("prime");
+        let constraints = constraints("watch", "prime");
+
+        for word in load_default_words() {
+            let contains_guessed_letter = guess.chars().any(|c| word.contains(c));
+
+            assert_eq!(
+                !contains_guessed_letter,
+                constraints.matches(&word),
+                "{}",
+                word
+            );
+        }
+    }
+
+    #[test]
+    fn given_all_black_pattern_when_violations_then_count_guessed_letters_in_word() {
+        let constraints = constraints("watch", "prime");
+
+        assert_eq!(0, constraints.violations(&Word::new("watch")));
+        assert_eq!(3, constraints.violations(&Word::new("crepe")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn when_to_solver_json_and_from_solver_json_then_json_and_matches_are_the_same() {
+        let words = load_default_words();
+        let games = [
+            ("cigar", "crust"),
+            ("stare", "stare"),
+            ("watch", "prime"),
+            ("toner", "poser"),
+            ("mourn", "hydro"),
+            ("stunt", "attic"),
+        ];
+
+        for (solution, guess) in games {
+            let constraints = constraints(solution, guess);
+            let json = constraints.to_solver_json();
+            let imported = Constraints::from_solver_json(&json).unwrap();
+
+            assert_eq!(json, imported.to_solver_json());
+
+            for word in &words {
+                assert_eq!(
+                    constraints.matches(word),
+                    imported.matches(word),
+                    "{}",
+                    word
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn given_letter_is_correct_and_black_when_to_solver_json_then_letter_is_absent() {
+        let constraints = constraints("store", "salsa");
+
+        assert_eq!(
+            r#"{"correct":{"0":"s"},"present":[],"absent":["a","l","s"]}"#,
+            constraints.to_solver_json()
+        );
+        assert!(Constraints::from_solver_json(&constraints.to_solver_json())
+            .unwrap()
+            .matches(&Word::new("store")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn given_fields_are_missing_when_from_solver_json_then_fields_are_empty() {
+        let constraints = Constraints::from_solver_json(r#"{"absent": ["e"]}"#).unwrap();
+
+        assert!(constraints.matches(&Word::new("fight")));
+        assert!(!constraints.matches(&Word::new("crane")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn given_escaped_letters_when_from_solver_json_then_letters_are_unescaped() {
+        let escaped = Constraints::from_solver_json(r#"{"correct": {"0": "\u0063"}}"#).unwrap();
+
+        assert_eq!(
+            r#"{"correct":{"0":"c"},"present":[],"absent":[]}"#,
+            escaped.to_solver_json()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn given_invalid_json_when_from_solver_json_then_return_error() {
+        assert!(Constraints::from_solver_json("").is_err());
+        assert!(Constraints::from_solver_json(r#"{"correct": {"5": "a"}}"#).is_err());
+        assert!(Constraints::from_solver_json(r#"{"present": [["ab", 1]]}"#).is_err());
+        assert!(Constraints::from_solver_json(r#"{"absent": ["a"]} x"#).is_err());
+        assert!(Constraints::from_solver_json(r#"{"unknown": []}"#).is_err());
+        assert!(
+            Constraints::from_solver_json(r#"{"present": [["a", 1]], "absent": ["a"]}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn given_word_of_another_length_when_matches_then_word_does_not_match() {
+        let constraints = Constraints::from_pattern(&Word::new("stare").self_pattern());
+
+        assert!(!constraints.matches(&Word::with_length("stares", 6).unwrap()));
+        assert!(!constraints.matches(&Word::with_length("star", 4).unwrap()));
+    }
+
+    #[test]
+    fn when_merge_then_matches_like_every_constraints_in_sequence() {
+        let words = load_default_words();
+        let patterns = [
+            constraints("fight", "soare"),
+            constraints("fight", "unlit"),
+            constraints("fight", "night"),
+        ];
+        let merged = Constraints::merge(&patterns);
+
+        for word in &words {
+            assert_eq!(
+                patterns.iter().all(|pattern| pattern.matches(word)),
+                merged.matches(word),
+                "{}",
+                word
+            );
+        }
+    }
+
+    #[test]
+    fn given_same_pattern_twice_when_merge_then_keep_constraints_once() {
+        let merged =
+            Constraints::merge(&[constraints("stare", "crane"), constraints("stare", "crane")]);
+
+        assert_eq!(
+            constraints("stare", "crane").iter().count(),
+            merged.iter().count()
+        );
+    }
+
+    #[test]
+    fn given_no_constraints_when_merge_then_every_word_matches() {
+        let merged = Constraints::merge(&[]);
+
+        assert!(merged.matches(&Word::new("crane")));
+        assert!(!merged.matches(&Word::with_length("cranes", 6).unwrap()));
+    }
+
+    #[test]
+    #[should_panic(expected = "different lengths")]
+    fn given_different_lengths_when_merge_then_panic() {
+        let long = Word::with_length("cranes", 6).unwrap();
+
+        Constraints::merge(&[
+            constraints("stare", "crane"),
+            Constraints::from_pattern(&long.self_pattern()),
+        ]);
+    }
+
+    #[test]
+    fn when_describe_then_describe_every_constraint_of_the_pattern() {
+        let descriptions = constraints("eerie", "geese").describe();
+
+        assert_eq!(
+            vec![
+                "position 1 must be 'e'",
+                "position 2 must not be 'e'",
+                "position 4 must be 'e'",
+                "letter 'e' appears at least 1 time (not at positions 1,4)",
+                "position 0 must not be 'g'",
+                "letter 'g' is absent",
+                "position 3 must not be 's'",
+                "letter 's' is absent",
+            ],
+            descriptions
+        );
+    }
+
+    #[test]
+    fn given_hand_built_constraints_when_merge_with_pattern_then_both_must_hold() {
+        let hand_built = Constraints::new(vec![Constraint::absent('E'), Constraint::lock(4, 't')]);
+        let merged = Constraints::merge(&[constraints("fight", "crane"), hand_built]);
+
+        assert!(merged.matches(&Word::new("fight")));
+        assert!(!merged.matches(&Word::new("lofty")));
+        assert!(!merged.matches(&Word::new("beset")));
+    }
+
+    #[test]
+    fn given_positions_past_the_end_when_matches_then_only_positions_within_the_word_count() {
+        let constraints = Constraints::new(vec![Constraint::at_most(0, vec![0, 1, 2, 3, 9], 'e')]);
+
+        assert!(constraints.matches(&Word::new("crane")));
+        assert!(!constraints.matches(&Word::new("beret")));
+        assert_eq!(
+            vec!["letter 'e' appears at most 0 times (not at positions 4)"],
+            constraints.describe()
+        );
+    }
+
+    #[test]
+    fn given_duplicate_positions_when_matches_then_each_position_counts_once() {
+        let absent = Constraints::new(vec![Constraint::at_most(0, vec![0, 0, 1, 2, 3], 'e')]);
+        let twice = Constraints::new(vec![Constraint::at_least(2, vec![4, 4], 'e')]);
+
+        assert!(absent.matches(&Word::new("crane")));
+        assert!(!absent.matches(&Word::new("beret")));
+        assert!(!twice.matches(&Word::new("crane")));
+    }
+
+    #[test]
+    fn given_longer_words_when_with_length_then_constraints_count_every_position() {
+        let constraints =
+            Constraints::with_length(vec![Constraint::at_least(2, (0..6).collect(), 'e')], 6);
+
+        assert!(constraints.matches(&Word::with_length("geezer", 6).unwrap()));
+        assert!(!constraints.matches(&Word::with_length("plumbs", 6).unwrap()));
+        assert!(!constraints.matches(&Word::new("geese")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn given_non_letter_when_contains_then_panic() {
+        Constraint::contains('1');
+    }
+
+    #[test]
+    fn when_reasons_then_tell_how_the_word_violates_each_constraint() {
+        assert_eq!(
+            vec![
+                "missing 'e' at position 1",
+                "missing 'e' at position 4",
+                "has absent letter 'g'",
+            ],
+            constraints("eerie", "geese").reasons(&Word::new("eager"))
+        );
+        assert_eq!(
+            vec![
+                "has forbidden 'e' at position 4",
+                "has more than 1 'e' (not at positions 1)",
+            ],
+            constraints("beret", "geese").reasons(&Word::new("tepee"))
+        );
+        assert_eq!(
+            vec!["missing required 'a'"],
+            constraints("crane", "shoal").reasons(&Word::new("drunk"))
+        );
+    }
+
+    #[test]
+    fn given_position_must_be_two_letters_when_contradiction_then_return_both_constraints() {
+        let constraints =
+            Constraints::merge(&[constraints("light", "light"), constraints("sight", "sight")]);
+
+        assert_eq!(
+            Some((
+                "position 0 must be 'l'".to_string(),
+                "position 0 must be 's'".to_string()
+            )),
+            constraints.contradiction()
+        );
+    }
+
+    #[test]
+    fn given_letter_is_present_and_absent_when_contradiction_then_return_both_constraints() {
+        let constraints =
+            Constraints::merge(&[constraints("fight", "tiger"), constraints("crane", "stomp")]);
+
+        assert_eq!(
+            Some((
+                "letter 't' appears at least 1 time".to_string(),
+                "letter 't' is absent".to_string()
+            )),
+            constraints.contradiction()
+        );
+    }
+
+    #[test]
+    fn given_patterns_of_the_same_solution_when_contradiction_then_return_none() {
+        let constraints = Constraints::merge(&[
+            constraints("eerie", "geese"),
+            constraints("eerie", "there"),
+            constraints("eerie", "eerie"),
+        ]);
+
+        assert_eq!(None, constraints.contradiction());
+    }
+
+    #[test]
+    fn given_latin_letters_when_matches_word_of_another_alphabet_then_only_count_its_letters() {
+        let russian = Alphabet::new("абвгдежзийклмнопрстуфхцчшщъыьэюя".chars()).unwrap();
+        let word = Word::with_alphabet("книга", 5, &russian).unwrap();
+
+        assert!(Constraints::new(vec![Constraint::absent('a')]).matches(&word));
+        assert!(Constraints::new(vec![Constraint::at_most(0, vec![0, 1], 'k')]).matches(&word));
+        assert!(!Constraints::new(vec![Constraint::contains('a')]).matches(&word));
+        assert!(!Constraints::new(vec![Constraint::absent('а')]).matches(&word));
+    }
 }
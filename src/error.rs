@@ -0,0 +1,99 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::pattern::HINT_CHARS;
+
+/// An error returned when words, hints, dictionaries or saved files cannot be read, so that
+/// callers may tell the kinds of errors apart. Each error is displayed as a message for users.
+///
+/// # Examples
+///
+/// ```
+/// # use std::str::FromStr;
+/// # use wools::{Word, WordsError};
+/// assert_eq!(
+///     Err(WordsError::WrongLength { expected: 5, actual: 3 }),
+///     Word::from_str("cut")
+/// );
+/// assert_eq!("word is not 5-character long", WordsError::WrongLength { expected: 5, actual: 3 }.to_string());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WordsError {
+    /// The word does not have the expected number of characters.
+    WrongLength { expected: usize, actual: usize },
+    /// The word contains characters which are not letters, even once transliterated.
+    NonAlphabetic,
+    /// The pattern does not have the expected number of hints.
+    WrongHintCount { expected: usize, actual: usize },
+    /// The character does not stand for any hint.
+    BadHintChar(char),
+    /// The dictionary cannot be read, with the message of the underlying I/O error.
+    DictionaryIo(String),
+    /// A line of the dictionary is not a valid word, lines starting at 1.
+    InvalidLine { line: usize, error: Box<WordsError> },
+    /// The alphabet has more letters than words can be made of.
+    TooManyLetters { max: usize, actual: usize },
+    /// A file cannot be read or written, with the message of the underlying I/O error.
+    Io(String),
+    /// A saved file or a dictionary line is not in the expected format, with a message telling why.
+    InvalidFormat(String),
+    /// A saved file was written in a version of its format which is not supported.
+    UnsupportedVersion(u32),
+}
+
+impl Display for WordsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WordsError::WrongLength { expected, .. } => {
+                write!(f, "word is not {}-character long", expected)
+            }
+            WordsError::NonAlphabetic => write!(f, "word contains non-alphabetical characters"),
+            WordsError::WrongHintCount { expected, .. } => {
+                write!(f, "pattern is not {}-character long", expected)
+            }
+            WordsError::BadHintChar(c) => {
+                let accepted = HINT_CHARS.map(|(c, _)| c.to_string());
+                write!(
+                    f,
+                    "unsupported hint '{}', expected {} or {}",
+                    c,
+                    accepted[..accepted.len() - 1].join(", "),
+                    accepted[accepted.len() - 1]
+                )
+            }
+            WordsError::DictionaryIo(message) => write!(f, "{}", message),
+            WordsError::InvalidLine { line, error } => write!(f, "line {}: {}", line, error),
+            WordsError::TooManyLetters { max, actual } => {
+                write!(f, "alphabet has {} letters, more than {}", actual, max)
+            }
+            WordsError::Io(message) | WordsError::InvalidFormat(message) => {
+                write!(f, "{}", message)
+            }
+            WordsError::UnsupportedVersion(version) => {
+                write!(f, "unsupported version {}", version)
+            }
+        }
+    }
+}
+
+impl Error for WordsError {}
+
+/// Tells I/O errors apart from files which are not valid JSON or not in the expected format.
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for WordsError {
+    fn from(error: serde_json::Error) -> Self {
+        if error.is_io() {
+            WordsError::Io(error.to_string())
+        } else {
+            WordsError::InvalidFormat(error.to_string())
+        }
+    }
+}
+
+/// Lets functions returning a message as error, such as most of this crate, use `?` on results
+/// with a [`WordsError`].
+impl From<WordsError> for String {
+    fn from(error: WordsError) -> Self {
+        error.to_string()
+    }
+}
@@ -0,0 +1,587 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::constraint::Constraints;
+use crate::pattern::{code, Hint, Pattern};
+use crate::word::Word;
+
+/// A game of Wordle against a known solution, limited to a number of guesses.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Game, GameStatus, Word};
+/// let mut game = Game::new(Word::new("apple"));
+///
+/// game.guess(&Word::new("prime")).unwrap();
+/// assert_eq!(GameStatus::InProgress, game.status());
+/// assert_eq!(5, game.remaining_guesses());
+///
+/// game.guess(&Word::new("apple")).unwrap();
+/// assert_eq!(GameStatus::Won, game.status());
+/// ```
+#[derive(Debug)]
+pub struct Game {
+    solution: Word,
+    max_guesses: usize,
+    patterns: Vec<Pattern>,
+}
+
+impl Game {
+    /// The number of guesses allowed in the standard game.
+    pub const DEFAULT_MAX_GUESSES: usize = 6;
+
+    /// Creates a game with [`Game::DEFAULT_MAX_GUESSES`] guesses to find the solution.
+    pub fn new(solution: Word) -> Self {
+        Game::with_max_guesses(solution, Game::DEFAULT_MAX_GUESSES)
+    }
+
+    /// Creates a game with a custom number of guesses to find the solution.
+    pub fn with_max_guesses(solution: Word, max_guesses: usize) -> Self {
+        Game {
+            solution,
+            max_guesses,
+            patterns: Vec::with_capacity(max_guesses),
+        }
+    }
+
+    /// Plays a guess, returning the pattern it produces, or an error if the game is over or if the
+    /// guess is not as long as the solution.
+    pub fn guess(&mut self, guess: &Word) -> Result<&Pattern, String> {
+        if self.status() != GameStatus::InProgress {
+            return Err("game is over".to_string());
+        }
+
+        self.patterns
+            .push(Pattern::try_from_solution_and_guess(&self.solution, guess)?);
+
+        Ok(self.patterns.last().unwrap())
+    }
+
+    /// Returns the status of the game.
+    pub fn status(&self) -> GameStatus {
+        match self.patterns.last() {
+            Some(pattern) if pattern.is_solved() => GameStatus::Won,
+            _ if self.patterns.len() >= self.max_guesses => GameStatus::Lost,
+            _ => GameStatus::InProgress,
+        }
+    }
+
+    /// Returns how many guesses may still be played.
+    pub fn remaining_guesses(&self) -> usize {
+        self.max_guesses.saturating_sub(self.patterns.len())
+    }
+
+    /// Returns the patterns produced by the guesses played so far.
+    pub fn patterns(&self) -> &[Pattern] {
+        &self.patterns
+    }
+
+    /// Returns the solution of the game.
+    pub fn solution(&self) -> &Word {
+        &self.solution
+    }
+}
+
+/// The status of a [`Game`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameStatus {
+    /// The solution has not been found, and guesses remain.
+    InProgress,
+    /// The solution has been found.
+    Won,
+    /// Every guess has been played without finding the solution.
+    Lost,
+}
+
+/// Plays a game against the solution, letting the strategy pick each guess among the remaining
+/// candidates, and returns the pattern produced by every guess.
+///
+/// The game ends once the solution is guessed, or when the strategy cannot make progress anymore,
+/// which happens when no candidate remains or when a guess does not eliminate any candidate.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{simulate, Word};
+/// let words = [Word::new("apple"), Word::new("prime"), Word::new("torch")];
+/// let patterns = simulate(&words, &Word::new("torch"), |candidates| candidates[0]);
+///
+/// assert_eq!(2, patterns.len());
+/// ```
+pub fn simulate<'a, F>(words: &'a [Word], solution: &Word, mut strategy: F) -> Vec<Pattern>
+where
+    F: FnMut(&[&'a Word]) -> &'a Word,
+{
+    play(words.iter().collect(), solution, &mut strategy)
+}
+
+fn play<'a, F>(mut candidates: Vec<&'a Word>, solution: &Word, strategy: &mut F) -> Vec<Pattern>
+where
+    F: FnMut(&[&'a Word]) -> &'a Word,
+{
+    let mut patterns = Vec::new();
+
+    while !candidates.is_empty() {
+        let pattern = Pattern::from_solution_and_guess(solution, strategy(&candidates));
+        let solved = pattern.is_solved();
+        let constraints = Constraints::from_pattern(&pattern);
+        patterns.push(pattern);
+
+        if solved {
+            break;
+        }
+
+        let count = candidates.len();
+        candidates.retain(|word| constraints.matches(word));
+
+        if candidates.len() == count {
+            break;
+        }
+    }
+
+    patterns
+}
+
+/// Simulates a game for each solution using the strategy, and returns the average score.
+///
+/// The score of a game is computed from its patterns by `score`; use [`guess_count`] to score
+/// games the standard way. Returns `0.0` when there are no solutions.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{evaluate, guess_count, Word};
+/// let words = [Word::new("apple"), Word::new("prime"), Word::new("torch")];
+/// let average = evaluate(&words, &words, |candidates| candidates[0], guess_count);
+///
+/// assert_eq!(5.0 / 3.0, average);
+/// ```
+pub fn evaluate<'a, F, S>(words: &'a [Word], solutions: &[Word], mut strategy: F, score: S) -> f64
+where
+    F: FnMut(&[&'a Word]) -> &'a Word,
+    S: Fn(&[Pattern]) -> f64,
+{
+    if solutions.is_empty() {
+        return 0.0;
+    }
+
+    let total = solutions
+        .iter()
+        .map(|solution| score(&simulate(words, solution, &mut strategy)))
+        .sum::<f64>();
+
+    total / solutions.len() as f64
+}
+
+/// Scores a game by its number of guesses, as done in the standard game.
+pub fn guess_count(patterns: &[Pattern]) -> f64 {
+    patterns.len() as f64
+}
+
+/// Measures how lucky the guesses of a completed game were, in guesses.
+///
+/// For each move, the number of guesses the strategy is expected to still need after the guess
+/// is averaged over every possible solution, then compared to the number it is expected to need
+/// from the position the guess actually led to. The differences are summed over the game: a
+/// positive luck means the hints were more helpful than expected, and a negative luck means they
+/// were less. The moves are expected to be consistent with the dictionary.
+///
+/// Expected guess counts are estimated by simulating the strategy, like [`evaluate`], from every
+/// position reachable by the moves, which is costly for large dictionaries.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{game_luck, Hint, Word};
+/// let dictionary = [Word::new("apple"), Word::new("prime"), Word::new("torch")];
+/// let moves = [(Word::new("torch"), [Hint::Green; wools::SIZE])];
+/// let luck = game_luck(&moves, |candidates| candidates[0], &dictionary);
+///
+/// assert!((luck - 2.0 / 3.0).abs() < 1e-9);
+/// ```
+pub fn game_luck<'a, H, F>(moves: &[(Word, H)], mut strategy: F, dictionary: &'a [Word]) -> f64
+where
+    H: AsRef<[Hint]>,
+    F: FnMut(&[&'a Word]) -> &'a Word,
+{
+    let mut candidates = dictionary.iter().collect::<Vec<&Word>>();
+    let mut luck = 0.0;
+
+    for (guess, hints) in moves {
+        let hints = hints.as_ref();
+        let mut buckets: HashMap<Vec<Hint>, Vec<&Word>> = HashMap::new();
+
+        for candidate in candidates.iter().copied() {
+            buckets
+                .entry(Pattern::from_solution_and_guess(candidate, guess).hints)
+                .or_default()
+                .push(candidate);
+        }
+
+        let expected = buckets
+            .iter()
+            .map(|(hints, bucket)| {
+                remaining_guesses(hints, bucket, &mut strategy) * bucket.len() as f64
+            })
+            .sum::<f64>()
+            / candidates.len().max(1) as f64;
+
+        candidates = buckets.remove(hints).unwrap_or_default();
+        luck += expected - remaining_guesses(hints, &candidates, &mut strategy);
+    }
+
+    luck
+}
+
+/// Responds to a guess like the adversarial host of Absurdle, which does not pick a solution
+/// upfront but answers each guess with the hints keeping as many words as possible, to make the
+/// game last. Returns the hints along with the words still possible after them, in the order of
+/// `words`. Words which are not as long as the guess are left out.
+///
+/// When several hints keep as many words, the ones with the fewest green hints, then the fewest
+/// yellow hints, are picked, being the least helpful to the player.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{absurdle_response, Hint, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("crane"), Word::new("sight")];
+/// let (hints, remaining) = absurdle_response(&words, &Word::new("light"));
+///
+/// assert_eq!([Hint::Black, Hint::Green, Hint::Green, Hint::Green, Hint::Green], *hints);
+/// assert_eq!(vec![&words[0], &words[1], &words[3]], remaining);
+/// ```
+pub fn absurdle_response<'a>(words: &'a [Word], guess: &Word) -> (Vec<Hint>, Vec<&'a Word>) {
+    let mut buckets: HashMap<Vec<Hint>, Vec<&Word>> = HashMap::new();
+
+    for word in words.iter().filter(|word| word.length() == guess.length()) {
+        buckets
+            .entry(Pattern::from_solution_and_guess(word, guess).hints)
+            .or_default()
+            .push(word);
+    }
+
+    buckets
+        .into_iter()
+        .max_by_key(|(hints, bucket)| {
+            let count = |hint: Hint| hints.iter().filter(|other| **other == hint).count();
+            (
+                bucket.len(),
+                Reverse(count(Hint::Green)),
+                Reverse(count(Hint::Yellow)),
+                Reverse(code(hints)),
+            )
+        })
+        .unwrap_or_else(|| (vec![Hint::Black; guess.length()], Vec::new()))
+}
+
+/// Tells whether a guess is legal in hard mode, given the previous guesses and their hints.
+///
+/// In hard mode, every revealed hint must be reused: each green letter must be guessed again at
+/// the same position, and each yellow letter must be guessed again anywhere, as many times as it
+/// was revealed by a single pattern. Unlike [`Constraints::matches`], this does not check that the
+/// guess could be the solution: a yellow letter may be guessed again at the same position, and
+/// black letters may be guessed again. A guess which is not as long as a previous one is illegal.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{is_valid_hard_mode_guess, Hint, Word};
+/// use Hint::{Black, Green, Yellow};
+///
+/// let previous = [(Word::new("crane"), [Black, Yellow, Green, Black, Black])];
+///
+/// assert!(is_valid_hard_mode_guess(&Word::new("roads"), &previous));
+/// assert!(is_valid_hard_mode_guess(&Word::new("trace"), &previous));
+/// assert!(!is_valid_hard_mode_guess(&Word::new("stain"), &previous));
+/// assert!(!is_valid_hard_mode_guess(&Word::new("laden"), &previous));
+/// ```
+pub fn is_valid_hard_mode_guess<H: AsRef<[Hint]>>(guess: &Word, previous: &[(Word, H)]) -> bool {
+    previous.iter().all(|(previous, hints)| {
+        let hints = hints.as_ref();
+
+        if guess.length() != previous.length() {
+            return false;
+        }
+
+        let mut required: HashMap<char, usize> = HashMap::new();
+
+        for ((expected, actual), hint) in previous.chars().zip(guess.chars()).zip(hints) {
+            match hint {
+                Hint::Green if expected != actual => return false,
+                Hint::Green | Hint::Yellow => *required.entry(expected).or_default() += 1,
+                Hint::Black => {}
+            }
+        }
+
+        required
+            .into_iter()
+            .all(|(c, count)| guess.count(c) >= count)
+    })
+}
+
+fn remaining_guesses<'a, F>(hints: &[Hint], candidates: &[&'a Word], strategy: &mut F) -> f64
+where
+    F: FnMut(&[&'a Word]) -> &'a Word,
+{
+    if candidates.is_empty() || hints.iter().all(Hint::is_green) {
+        return 0.0;
+    }
+
+    let total = candidates
+        .iter()
+        .map(|solution| play(candidates.to_vec(), solution, strategy).len())
+        .sum::<usize>();
+
+    total as f64 / candidates.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        absurdle_response, evaluate, game_luck, guess_count, is_valid_hard_mode_guess, simulate,
+        Game, GameStatus,
+    };
+    use crate::{Hint, Pattern, Word};
+
+    fn words() -> Vec<Word> {
+        ["apple", "prime", "plume", "torch"]
+            .into_iter()
+            .map(Word::new)
+            .collect()
+    }
+
+    fn tile_count(patterns: &[Pattern]) -> f64 {
+        patterns
+            .iter()
+            .flat_map(|pattern| pattern.hints())
+            .filter(|hint| !hint.is_black())
+            .count() as f64
+    }
+
+    #[test]
+    fn when_simulate_then_game_ends_when_solution_is_guessed() {
+        let words = words();
+        let patterns = simulate(&words, &Word::new("torch"), |candidates| candidates[0]);
+
+        assert_eq!(2, patterns.len());
+        assert_eq!(Word::new("apple"), patterns[0].guess);
+        assert!(patterns[1].is_solved());
+    }
+
+    #[test]
+    fn given_solution_is_not_a_word_when_simulate_then_game_ends_without_solving() {
+        let words = words();
+        let patterns = simulate(&words, &Word::new("watch"), |candidates| candidates[0]);
+
+        assert_eq!(1, patterns.len());
+        assert!(!patterns[0].is_solved());
+    }
+
+    #[test]
+    fn given_guess_count_when_evaluate_then_return_average_guess_count() {
+        let words = words();
+        let solutions = [Word::new("apple"), Word::new("torch")];
+        let average = evaluate(&words, &solutions, |candidates| candidates[0], guess_count);
+
+        assert_eq!(1.5, average);
+    }
+
+    #[test]
+    fn given_custom_score_when_evaluate_then_return_average_custom_score() {
+        let words = words();
+        let solutions = [Word::new("prime"), Word::new("torch")];
+        let average = evaluate(&words, &solutions, |candidates| candidates[0], tile_count);
+
+        // prime: apple (p, e), prime (5 tiles); torch: apple (none), torch (5 tiles).
+        assert_eq!(6.0, average);
+    }
+
+    #[test]
+    fn given_no_solutions_when_evaluate_then_return_zero() {
+        let words = words();
+        let average = evaluate(&words, &[], |candidates| candidates[0], guess_count);
+
+        assert_eq!(0.0, average);
+    }
+
+    #[test]
+    fn given_solution_is_guessed_first_when_game_luck_then_luck_is_positive() {
+        let dictionary = [Word::new("apple"), Word::new("prime"), Word::new("torch")];
+        let moves = [(Word::new("torch"), [Hint::Green; Word::SIZE])];
+        let luck = game_luck(&moves, |candidates| candidates[0], &dictionary);
+
+        // Guessing "torch" leaves one guess to play for "apple" and "prime", and none for "torch".
+        assert!((luck - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn given_first_guess_is_not_the_solution_when_game_luck_then_luck_is_negative() {
+        let dictionary = [Word::new("apple"), Word::new("prime"), Word::new("torch")];
+        let moves = [
+            (Word::new("apple"), [Hint::Black; Word::SIZE]),
+            (Word::new("torch"), [Hint::Green; Word::SIZE]),
+        ];
+        let luck = game_luck(&moves, |candidates| candidates[0], &dictionary);
+
+        // Guessing "apple" was expected to leave 2/3 guesses to play, but left one.
+        assert!((luck + 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn given_solution_is_guessed_when_status_then_game_is_won() {
+        let mut game = Game::new(Word::new("apple"));
+
+        game.guess(&Word::new("apple")).unwrap();
+
+        assert_eq!(GameStatus::Won, game.status());
+        assert_eq!(Game::DEFAULT_MAX_GUESSES - 1, game.remaining_guesses());
+    }
+
+    #[test]
+    fn given_every_guess_is_wrong_when_status_then_game_is_lost() {
+        let mut game = Game::with_max_guesses(Word::new("apple"), 2);
+
+        game.guess(&Word::new("prime")).unwrap();
+        assert_eq!(GameStatus::InProgress, game.status());
+        game.guess(&Word::new("plume")).unwrap();
+
+        assert_eq!(GameStatus::Lost, game.status());
+        assert_eq!(0, game.remaining_guesses());
+    }
+
+    #[test]
+    fn given_game_is_over_when_guess_then_return_error() {
+        let mut game = Game::with_max_guesses(Word::new("apple"), 1);
+
+        game.guess(&Word::new("prime")).unwrap();
+
+        assert!(game.guess(&Word::new("apple")).is_err());
+        assert_eq!(1, game.patterns().len());
+    }
+
+    #[test]
+    fn given_guess_is_not_as_long_as_solution_when_guess_then_return_error() {
+        let mut game = Game::new(Word::new("apple"));
+
+        assert!(game.guess(&Word::with_length("pear", 4).unwrap()).is_err());
+        assert!(game.patterns().is_empty());
+    }
+
+    #[test]
+    fn given_green_hint_when_is_valid_hard_mode_guess_then_letter_must_stay_in_place() {
+        let previous = [(
+            Word::new("crane"),
+            [
+                Hint::Green,
+                Hint::Black,
+                Hint::Black,
+                Hint::Black,
+                Hint::Black,
+            ],
+        )];
+
+        assert!(is_valid_hard_mode_guess(&Word::new("cloud"), &previous));
+        assert!(!is_valid_hard_mode_guess(&Word::new("occur"), &previous));
+    }
+
+    #[test]
+    fn given_yellow_hints_when_is_valid_hard_mode_guess_then_letters_must_be_reused_as_often() {
+        let previous = [(
+            Word::new("geese"),
+            [
+                Hint::Black,
+                Hint::Yellow,
+                Hint::Yellow,
+                Hint::Black,
+                Hint::Black,
+            ],
+        )];
+
+        assert!(is_valid_hard_mode_guess(&Word::new("eerie"), &previous));
+        assert!(is_valid_hard_mode_guess(&Word::new("theme"), &previous));
+        assert!(!is_valid_hard_mode_guess(&Word::new("crane"), &previous));
+    }
+
+    #[test]
+    fn given_several_previous_guesses_when_is_valid_hard_mode_guess_then_obey_every_one() {
+        let previous = [
+            (
+                Word::new("crane"),
+                [
+                    Hint::Black,
+                    Hint::Black,
+                    Hint::Green,
+                    Hint::Black,
+                    Hint::Black,
+                ],
+            ),
+            (
+                Word::new("ghost"),
+                [
+                    Hint::Black,
+                    Hint::Black,
+                    Hint::Black,
+                    Hint::Yellow,
+                    Hint::Black,
+                ],
+            ),
+        ];
+
+        assert!(is_valid_hard_mode_guess(&Word::new("feast"), &previous));
+        assert!(!is_valid_hard_mode_guess(&Word::new("plaid"), &previous));
+        assert!(!is_valid_hard_mode_guess(&Word::new("sword"), &previous));
+    }
+
+    #[test]
+    fn given_no_previous_guesses_when_is_valid_hard_mode_guess_then_every_guess_is_valid() {
+        assert!(is_valid_hard_mode_guess::<Vec<Hint>>(
+            &Word::new("crane"),
+            &[]
+        ));
+    }
+
+    #[test]
+    fn when_absurdle_response_then_keep_the_largest_set_of_words() {
+        let words = [
+            Word::new("fight"),
+            Word::new("crane"),
+            Word::new("might"),
+            Word::new("light"),
+        ];
+        let (hints, remaining) = absurdle_response(&words, &Word::new("sight"));
+
+        assert_eq!(
+            Pattern::from_solution_and_guess(&words[0], &Word::new("sight")).hints,
+            hints
+        );
+        assert_eq!(vec![&words[0], &words[2], &words[3]], remaining);
+    }
+
+    #[test]
+    fn given_tied_sets_when_absurdle_response_then_pick_fewest_greens() {
+        let words = [Word::new("fight"), Word::new("crane")];
+        let (hints, remaining) = absurdle_response(&words, &Word::new("night"));
+
+        assert_eq!(
+            [
+                Hint::Yellow,
+                Hint::Black,
+                Hint::Black,
+                Hint::Black,
+                Hint::Black
+            ],
+            *hints
+        );
+        assert_eq!(vec![&words[1]], remaining);
+    }
+
+    #[test]
+    fn given_no_words_when_absurdle_response_then_every_hint_is_black() {
+        let (hints, remaining) = absurdle_response(&[], &Word::new("crane"));
+
+        assert_eq!([Hint::Black; 5], *hints);
+        assert!(remaining.is_empty());
+    }
+}
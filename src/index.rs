@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use crate::alphabet::Alphabet;
+use crate::constraint::{Constraint, Constraints};
+use crate::pattern::{Hint, Pattern};
+use crate::word::Word;
+
+const ALPHABET_SIZE: usize = Alphabet::MAX_LETTERS;
+
+/// A dictionary indexed once to answer many queries quickly.
+///
+/// For each letter and position, the index holds the set of words having the letter at the
+/// position, for each letter and count, the set of words containing the letter exactly that many
+/// times, and for each length, the set of words of that length. Sets are stored as bitsets over
+/// the words, so matching constraints comes down to intersecting and merging bitsets instead of
+/// going through every character of every word.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Hint, IndexedDictionary, Word};
+/// let dictionary = IndexedDictionary::new(vec![Word::new("cargo"), Word::new("babel"), Word::new("orbit")]);
+/// let hints = [Hint::Black, Hint::Green, Hint::Black, Hint::Black, Hint::Black];
+///
+/// assert_eq!(
+///     vec![&Word::new("cargo"), &Word::new("babel")],
+///     dictionary.solve(&[(Word::new("pants"), hints)])
+/// );
+/// ```
+#[derive(Debug)]
+pub struct IndexedDictionary {
+    words: Vec<Word>,
+    positions: Vec<[Bitset; ALPHABET_SIZE]>,
+    counts: [Vec<Bitset>; ALPHABET_SIZE],
+    lengths: HashMap<usize, Bitset>,
+}
+
+impl IndexedDictionary {
+    /// Indexes the words.
+    pub fn new(words: Vec<Word>) -> Self {
+        let empty = Bitset::empty(words.len());
+        let length = words.iter().map(Word::length).max().unwrap_or(0);
+        let mut positions = vec![std::array::from_fn(|_| empty.clone()); length];
+        let mut counts = std::array::from_fn(|_| vec![empty.clone(); length + 1]);
+        let mut lengths = HashMap::new();
+
+        for (i, word) in words.iter().enumerate() {
+            lengths
+                .entry(word.length())
+                .or_insert_with(|| empty.clone())
+                .insert(i);
+
+            let mut letter_counts = [0; ALPHABET_SIZE];
+
+            for (position, &letter) in word.letters().iter().enumerate() {
+                positions[position][letter as usize].insert(i);
+                letter_counts[letter as usize] += 1;
+            }
+
+            for (letter, count) in letter_counts.into_iter().enumerate() {
+                counts[letter][count].insert(i);
+            }
+        }
+
+        IndexedDictionary {
+            words,
+            positions,
+            counts,
+            lengths,
+        }
+    }
+
+    /// Returns the indexed words.
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    /// Filters out the words using the guesses and hints, so that only the possible solutions
+    /// remain. This is equivalent to [`crate::solve`].
+    pub fn solve<H: AsRef<[Hint]>>(&self, guesses_and_hints: &[(Word, H)]) -> Vec<&Word> {
+        let mut matches = Bitset::full(self.words.len());
+
+        for (guess, hints) in guesses_and_hints {
+            let constraints =
+                Constraints::from_pattern(&Pattern::from_guess_and_hints(guess, hints.as_ref()));
+
+            match self.lengths.get(&guess.length()) {
+                Some(words) => matches.intersect(words),
+                None => return Vec::new(),
+            }
+
+            for constraint in constraints.iter() {
+                matches.intersect(&self.matches(constraint, guess));
+            }
+        }
+
+        matches.iter().map(|i| &self.words[i]).collect()
+    }
+
+    /// Finds the words matching a constraint of the pattern of the guess, whose letters are those
+    /// of the guess.
+    fn matches(&self, constraint: &Constraint, guess: &Word) -> Bitset {
+        let (positions, char, accepts): (&[usize], char, &dyn Fn(usize) -> bool) = match constraint
+        {
+            Constraint::AtLeast {
+                positions,
+                count,
+                char,
+            } => (positions, *char, &move |n| n >= *count),
+            Constraint::AtMost {
+                positions,
+                count,
+                char,
+            } => (positions, *char, &move |n| n <= *count),
+        };
+        let letter = guess
+            .letter(char)
+            .expect("constraints of a pattern are on letters of its guess")
+            as usize;
+        let length = guess.length();
+        let mut matches = Bitset::empty(self.words.len());
+
+        if positions.len() == length {
+            for (n, words) in self.counts[letter].iter().enumerate() {
+                if accepts(n) {
+                    matches.union(words);
+                }
+            }
+        } else {
+            // exact[n] holds the words having the letter exactly n times among the positions seen.
+            let mut exact = vec![Bitset::empty(self.words.len()); positions.len() + 1];
+            exact[0] = Bitset::full(self.words.len());
+
+            // Positions past the end of every indexed word have no letter, hence count nothing.
+            for (seen, words) in positions
+                .iter()
+                .filter_map(|&position| self.positions.get(position))
+                .map(|letters| &letters[letter])
+                .enumerate()
+            {
+                for n in (0..=seen).rev() {
+                    let with = exact[n].intersection(words);
+                    exact[n].subtract(words);
+                    exact[n + 1].union(&with);
+                }
+            }
+
+            for (n, words) in exact.iter().enumerate() {
+                if accepts(n) {
+                    matches.union(words);
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Bitset {
+    blocks: Vec<u64>,
+    len: usize,
+}
+
+impl Bitset {
+    fn empty(len: usize) -> Self {
+        Bitset {
+            blocks: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    fn full(len: usize) -> Self {
+        let mut bitset = Bitset {
+            blocks: vec![u64::MAX; len.div_ceil(64)],
+            len,
+        };
+
+        if !len.is_multiple_of(64) {
+            *bitset.blocks.last_mut().unwrap() = (1 << (len % 64)) - 1;
+        }
+
+        bitset
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.blocks[i / 64] |= 1 << (i % 64);
+    }
+
+    fn intersect(&mut self, other: &Bitset) {
+        for (block, other) in self.blocks.iter_mut().zip(&other.blocks) {
+            *block &= other;
+        }
+    }
+
+    fn intersection(&self, other: &Bitset) -> Bitset {
+        let mut intersection = self.clone();
+        intersection.intersect(other);
+        intersection
+    }
+
+    fn union(&mut self, other: &Bitset) {
+        for (block, other) in self.blocks.iter_mut().zip(&other.blocks) {
+            *block |= other;
+        }
+    }
+
+    fn subtract(&mut self, other: &Bitset) {
+        for (block, other) in self.blocks.iter_mut().zip(&other.blocks) {
+            *block &= !other;
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|i| self.blocks[i / 64] & (1 << (i % 64)) != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index::IndexedDictionary;
+    use crate::{load_default_words, solve, Hint, Pattern, Word};
+
+    #[test]
+    fn when_solve_then_return_the_same_words_as_solve() {
+        let dictionary = IndexedDictionary::new(load_default_words());
+        let words = load_default_words();
+        let games = [
+            ("mourn", vec!["stare", "hydro"]),
+            ("tonal", vec!["swoop"]),
+            ("leech", vec!["tepee", "eerie"]),
+            ("mamma", vec!["madam", "gamma", "mommy"]),
+            ("stare", vec!["stare"]),
+            ("fuzzy", vec![]),
+        ];
+
+        for (solution, guesses) in games {
+            let guesses_and_hints = guesses
+                .into_iter()
+                .map(|guess| {
+                    let pattern =
+                        Pattern::from_solution_and_guess(&Word::new(solution), &Word::new(guess));
+                    (pattern.guess, pattern.hints)
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                solve(&words, &guesses_and_hints),
+                dictionary.solve(&guesses_and_hints)
+            );
+        }
+    }
+
+    #[test]
+    fn given_no_words_when_solve_then_return_no_words() {
+        let dictionary = IndexedDictionary::new(vec![]);
+
+        assert!(dictionary.solve::<Vec<Hint>>(&[]).is_empty());
+    }
+}
@@ -1,17 +1,79 @@
 //! Tools for the Wordle game.
 
-pub use crate::constraint::Constraints;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+pub use crate::alphabet::Alphabet;
+pub use crate::analysis::{
+    candidate_heatmap, dictionary_stats, greedy_decision_sequence, indistinguishable_pairs,
+    DictStats,
+};
+pub use crate::cache::PatternCache;
+pub use crate::constraint::{Constraint, Constraints};
+pub use crate::error::WordsError;
+pub use crate::game::{
+    absurdle_response, evaluate, game_luck, guess_count, is_valid_hard_mode_guess, simulate, Game,
+    GameStatus,
+};
+pub use crate::index::IndexedDictionary;
+pub use crate::opener::{best_openers, load_opener_table, save_opener_table, OpenerTable};
+#[cfg(feature = "serde")]
+pub use crate::opener::{load_best_openers, save_best_openers};
 pub use crate::pattern::{Hint, Pattern};
+pub use crate::solver::{MultiBoard, Solver};
+#[cfg(feature = "rayon")]
+pub use crate::strategy::best_guess_parallel;
+pub use crate::strategy::{
+    best_guess, best_guess_adaptive, best_guess_expected_remaining, entropy, expected_remaining,
+    minimax_guess, partition, pattern_distribution, positional_score, rank_by_entropy,
+    rank_by_positional, rank_guesses, rank_guesses_avoiding_absent,
+    rank_guesses_favoring_candidates, rank_guesses_with_constraints, rank_minimax, win_probability,
+    worst_case_remaining, DEFAULT_ABSENT_LETTER_WEIGHT, DEFAULT_ENDGAME_THRESHOLD,
+    DEFAULT_WIN_PROBABILITY_WEIGHT,
+};
+pub use crate::tree::DecisionTree;
 pub use crate::word::Word;
-pub use crate::wordlist::load_default_words;
+pub use crate::wordlist::{
+    default_words_len, default_words_raw, load_default_words, load_weighted_words_iter,
+    load_words_iter, parse_weighted_word, WordList, DEFAULT_FREQUENCY,
+};
 
+mod alphabet;
+mod analysis;
+mod cache;
 mod constraint;
+mod error;
+mod game;
+mod index;
+mod opener;
 mod pattern;
+mod solver;
+mod strategy;
+mod tree;
 mod word;
 mod wordlist;
 
+/// The number of characters of words by default, same as [`Word::SIZE`].
+///
+/// Hint arrays for such words should be declared with this size rather than a literal, e.g.
+/// `[Hint; wools::SIZE]`, since hints which do not have one hint per character of their guess are
+/// rejected by the functions taking hints:
+///
+/// ```should_panic
+/// # use wools::{Hint, Pattern, Word};
+/// Pattern::from_guess_and_hints(&Word::new("apple"), &[Hint::Green; 4]);
+/// ```
+pub const SIZE: usize = Word::SIZE;
+
 /// Filters out the words using the solution and the guesses, so that only the possible solutions
-/// remain.
+/// remain. Words which are not as long as the solution are filtered out.
+///
+/// The guesses may be a slice as well as any iterator of words, e.g. guesses generated lazily.
+///
+/// # Panics
+///
+/// Panics if a guess is not as long as the solution.
 ///
 /// # Examples
 ///
@@ -21,20 +83,168 @@ mod wordlist;
 /// let solutions = filter(&words, &Word::new("apple"), &[Word::new("prime")]);
 ///
 /// assert_eq!(vec!(&Word::new("apple")), solutions);
+///
+/// let guesses = ["prime", "plumb"].into_iter().map(Word::new).collect::<Vec<_>>();
+/// assert_eq!(solutions, filter(&words, &Word::new("apple"), guesses.iter().rev()));
 /// ```
-pub fn filter<'a>(words: &'a [Word], solution: &Word, guesses: &[Word]) -> Vec<&'a Word> {
+#[must_use]
+pub fn filter<'a, 'g, I>(words: &'a [Word], solution: &Word, guesses: I) -> Vec<&'a Word>
+where
+    I: IntoIterator<Item = &'g Word>,
+{
     let constraints = guesses
-        .iter()
+        .into_iter()
+        .map(|guess| Constraints::from_pattern(&Pattern::from_solution_and_guess(solution, guess)))
+        .collect();
+
+    allowed_by(words, constraints)
+        .filter(|word| word.length() == solution.length())
+        .collect()
+}
+
+/// Filters out the words like [`filter`], lazily yielding the possible solutions instead of
+/// collecting them, e.g. to count them or to only take the first few. The guesses may be any
+/// iterator of words, like for [`filter`].
+///
+/// # Panics
+///
+/// Panics if a guess is not as long as the solution.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{filter_iter, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("light"), Word::new("crane")];
+/// let solution = Word::new("fight");
+///
+/// assert_eq!(2, filter_iter(&words, &solution, &[Word::new("stomp")]).count());
+/// assert_eq!(
+///     vec![&words[0]],
+///     filter_iter(&words, &solution, &[Word::new("stomp")]).take(1).collect::<Vec<_>>()
+/// );
+/// ```
+pub fn filter_iter<'a, 'g, I>(
+    words: &'a [Word],
+    solution: &Word,
+    guesses: I,
+) -> impl Iterator<Item = &'a Word>
+where
+    I: IntoIterator<Item = &'g Word>,
+{
+    let constraints = guesses
+        .into_iter()
         .map(|guess| Constraints::from_pattern(&Pattern::from_solution_and_guess(solution, guess)))
-        .collect::<Vec<Constraints>>();
+        .collect();
+    let length = solution.length();
 
+    allowed_by(words, constraints).filter(move |word| word.length() == length)
+}
+
+/// Lazily yields the words which every constraint allows. The constraints are built beforehand,
+/// so that the iterator only borrows the words.
+fn allowed_by(words: &[Word], constraints: Vec<Constraints>) -> impl Iterator<Item = &Word> {
     words
         .iter()
-        .filter(|word| constraints.iter().all(|pattern| pattern.matches(word)))
-        .collect()
+        .filter(move |word| constraints.iter().all(|pattern| pattern.matches(word)))
+}
+
+/// Lazily builds the constraints of each guess and its hints, in order.
+fn constraints_of<G, H, I>(guesses_and_hints: I) -> impl Iterator<Item = Constraints>
+where
+    G: Borrow<(Word, H)>,
+    H: AsRef<[Hint]>,
+    I: IntoIterator<Item = G>,
+{
+    guesses_and_hints.into_iter().map(|guess_and_hints| {
+        let (guess, hints) = guess_and_hints.borrow();
+        Constraints::from_pattern(&Pattern::from_guess_and_hints(guess, hints.as_ref()))
+    })
+}
+
+/// Filters out the words using the guesses like [`filter`], then removes the excluded words, such
+/// as words already known not to be the solution. Words are compared once normalized, so the
+/// excluded words may be written with any casing or accents. The guesses may be any iterator of
+/// words, like for [`filter`].
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{filter_excluding, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("light")];
+/// let solution = Word::new("fight");
+/// let remaining = filter_excluding(&words, &solution, &[Word::new("crane")], &[Word::new("MIGHT")]);
+///
+/// assert_eq!(vec![&words[0], &words[2]], remaining);
+/// ```
+#[must_use]
+pub fn filter_excluding<'a, 'g, I>(
+    words: &'a [Word],
+    solution: &Word,
+    guesses: I,
+    exclude: &[Word],
+) -> Vec<&'a Word>
+where
+    I: IntoIterator<Item = &'g Word>,
+{
+    let mut words = filter(words, solution, guesses);
+    words.retain(|word| !exclude.contains(word));
+    words
+}
+
+/// Finds the words matching a template of known letters, such as `_a__e`, where `_` stands for
+/// any letter. Unlike hints, the template tells nothing about the other letters, which may appear
+/// anywhere, including at the wildcards. Letters are compared case-insensitively.
+///
+/// Returns an error if the template does not have [`Word::SIZE`] characters, or if it contains
+/// characters other than letters and `_`.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{filter_template, Word};
+/// let words = [Word::new("cable"), Word::new("nacre"), Word::new("later"), Word::new("eagle")];
+///
+/// assert_eq!(Ok(vec![&words[0], &words[1], &words[3]]), filter_template(&words, "_a__e"));
+/// assert!(filter_template(&words, "_a_e").is_err());
+/// ```
+pub fn filter_template<'a>(words: &'a [Word], template: &str) -> Result<Vec<&'a Word>, WordsError> {
+    let actual = template.chars().count();
+
+    if actual != Word::SIZE {
+        return Err(WordsError::WrongLength {
+            expected: Word::SIZE,
+            actual,
+        });
+    }
+
+    let template = template
+        .chars()
+        .map(|c| match c {
+            '_' => Ok(None),
+            c if c.is_ascii_alphabetic() => Ok(Some(c.to_ascii_lowercase())),
+            _ => Err(WordsError::NonAlphabetic),
+        })
+        .collect::<Result<Vec<Option<char>>, WordsError>>()?;
+
+    Ok(words
+        .iter()
+        .filter(|word| {
+            word.length() == template.len()
+                && word
+                    .chars()
+                    .zip(&template)
+                    .all(|(c, fixed)| fixed.is_none_or(|fixed| fixed == c))
+        })
+        .collect())
 }
 
-/// Finds the words which produce the same hints given the solution.
+/// Finds the words which produce the same hints given the solution. Words which are not as long
+/// as the solution never match. The hints may be an array as well as a slice, e.g. hints parsed
+/// from variable input.
+///
+/// # Panics
+///
+/// Panics if there is not exactly one hint per character of the solution.
 ///
 /// # Examples
 ///
@@ -46,18 +256,137 @@ pub fn filter<'a>(words: &'a [Word], solution: &Word, guesses: &[Word]) -> Vec<&
 ///
 /// assert_eq!(vec!(&Word::new("babel")), matches);
 /// ```
-pub fn matches<'a>(
-    words: &'a [Word],
-    solution: &Word,
-    hints: &[Hint; Word::SIZE],
-) -> Vec<&'a Word> {
+#[must_use]
+pub fn matches<'a>(words: &'a [Word], solution: &Word, hints: &[Hint]) -> Vec<&'a Word> {
+    matches_any(words, std::slice::from_ref(solution), hints)
+}
+
+/// Finds the words which produce the hints given at least one of the solutions, e.g. in game
+/// modes such as Dordle or Quordle where several boards share each guess. This is the union of
+/// [`matches()`] for every solution, where [`matches_all`] is the intersection. Words are kept in
+/// the order of `words`, and no word matches when there are no solutions.
+///
+/// # Panics
+///
+/// Panics if there is not exactly one hint per character of a solution.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Hint, matches_any, Word};
+/// let words = [Word::new("plumb"), Word::new("dowry"), Word::new("stomp"), Word::new("trace")];
+/// let solutions = [Word::new("fight"), Word::new("crane")];
+/// let matches = matches_any(&words, &solutions, &[Hint::Black; 5]);
+///
+/// assert_eq!(vec![&words[0], &words[1], &words[2]], matches);
+/// ```
+#[must_use]
+pub fn matches_any<'a>(words: &'a [Word], solutions: &[Word], hints: &[Hint]) -> Vec<&'a Word> {
+    assert_one_hint_per_character(solutions, hints);
+
     words
         .iter()
-        .filter(|word| Pattern::from_solution_and_guess(solution, word).hints == *hints)
+        .filter(|word| {
+            solutions
+                .iter()
+                .any(|solution| produces_hints(solution, word, hints))
+        })
         .collect()
 }
 
+/// Finds the words which produce the hints given every one of the solutions. This is the
+/// intersection of [`matches()`] for every solution, where [`matches_any`] is the union. Words are
+/// kept in the order of `words`, and every word matches when there are no solutions.
+///
+/// # Panics
+///
+/// Panics if there is not exactly one hint per character of a solution.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Hint, matches_all, Word};
+/// let words = [Word::new("plumb"), Word::new("dowry"), Word::new("stomp"), Word::new("trace")];
+/// let solutions = [Word::new("fight"), Word::new("crane")];
+/// let matches = matches_all(&words, &solutions, &[Hint::Black; 5]);
+///
+/// assert_eq!(vec![&words[0]], matches);
+/// ```
+#[must_use]
+pub fn matches_all<'a>(words: &'a [Word], solutions: &[Word], hints: &[Hint]) -> Vec<&'a Word> {
+    assert_one_hint_per_character(solutions, hints);
+
+    words
+        .iter()
+        .filter(|word| {
+            solutions
+                .iter()
+                .all(|solution| produces_hints(solution, word, hints))
+        })
+        .collect()
+}
+
+fn assert_one_hint_per_character(solutions: &[Word], hints: &[Hint]) {
+    for solution in solutions {
+        assert_eq!(
+            solution.length(),
+            hints.len(),
+            "solution {} does not have one hint per character",
+            solution
+        );
+    }
+}
+
+fn produces_hints(solution: &Word, word: &Word, hints: &[Hint]) -> bool {
+    word.length() == solution.length()
+        && Pattern::from_solution_and_guess(solution, word).hints == hints
+}
+
+/// Finds the words which produce the same hints given the solution, like [`matches()`], sorted
+/// from the most to the least plausible according to the scorer.
+///
+/// The scorer rates how likely a word is to be the intended one, e.g. from its letter frequencies
+/// or from word frequency metadata. Words with equal scores are kept in the order of `words`.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Hint, matches_ranked, Word};
+/// let words = [Word::new("babel"), Word::new("cargo"), Word::new("waltz")];
+/// let hints = [Hint::Black, Hint::Green, Hint::Black, Hint::Black, Hint::Black];
+/// let rarity = |word: &Word| -(word.chars().filter(|c| "jqxz".contains(*c)).count() as f64);
+/// let matches = matches_ranked(&words, &Word::new("cargo"), &hints, &rarity);
+///
+/// assert_eq!(vec!(&Word::new("babel"), &Word::new("waltz")), matches);
+/// ```
+#[must_use]
+pub fn matches_ranked<'a, F>(
+    words: &'a [Word],
+    solution: &Word,
+    hints: &[Hint],
+    scorer: &F,
+) -> Vec<&'a Word>
+where
+    F: Fn(&Word) -> f64,
+{
+    let mut matches = matches(words, solution, hints)
+        .into_iter()
+        .map(|word| (word, scorer(word)))
+        .collect::<Vec<_>>();
+
+    matches.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+    matches.into_iter().map(|(word, _)| word).collect()
+}
+
 /// Filters out the words using the guesses and hints, so that only the possible solutions remain.
+/// Words which are not as long as the guesses are filtered out, but nothing is filtered out when
+/// no guesses are given, since the length of the solution is then unknown.
+///
+/// The guesses and hints may be a slice as well as any iterator of them, like for [`filter`].
+///
+/// # Panics
+///
+/// Panics if there is not exactly one hint per character of a guess.
 ///
 /// # Examples
 ///
@@ -68,26 +397,349 @@ pub fn matches<'a>(
 /// let hints = [Hint::Black, Hint::Green, Hint::Black, Hint::Black, Hint::Black];
 /// let solutions = solve(&words, &[(guess, hints)]);
 /// ```
-pub fn solve<'a>(
+#[must_use]
+pub fn solve<'a, 'g, H, I>(words: &'a [Word], guesses_and_hints: I) -> Vec<&'a Word>
+where
+    H: AsRef<[Hint]> + 'g,
+    I: IntoIterator<Item = &'g (Word, H)>,
+{
+    let constraints = constraints_of(guesses_and_hints).collect();
+
+    allowed_by(words, constraints).collect()
+}
+
+/// Counts the possible solutions left after each guess and its hints, in order, to see how fast
+/// the candidates narrowed down over a game. The last count is the number of words [`solve`]
+/// finds. The words are filtered incrementally, each guess only filtering the words left by the
+/// previous ones. The guesses and hints may be any iterator of them, like for [`solve`].
+///
+/// # Panics
+///
+/// Panics if there is not exactly one hint per character of a guess.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{solve_trace, Pattern, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("light"), Word::new("crane")];
+/// let solution = Word::new("light");
+/// let history = ["crane", "fight", "might"]
+///     .map(|guess| Pattern::from_solution_and_guess(&solution, &Word::new(guess)))
+///     .map(|pattern| (pattern.guess, pattern.hints));
+///
+/// assert_eq!(vec![3, 2, 1], solve_trace(&words, &history));
+/// ```
+#[must_use]
+pub fn solve_trace<'g, H, I>(words: &[Word], guesses_and_hints: I) -> Vec<usize>
+where
+    H: AsRef<[Hint]> + 'g,
+    I: IntoIterator<Item = &'g (Word, H)>,
+{
+    let mut remaining = words.iter().collect::<Vec<&Word>>();
+
+    constraints_of(guesses_and_hints)
+        .map(|constraints| {
+            remaining.retain(|word| constraints.matches(word));
+            remaining.len()
+        })
+        .collect()
+}
+
+/// Filters out the words using the guesses and hints like [`solve`], lazily yielding the possible
+/// solutions instead of collecting them, e.g. to only show the first page of a long list. The
+/// guesses and hints may be any iterator of them, like for [`solve`].
+///
+/// # Panics
+///
+/// Panics if there is not exactly one hint per character of a guess.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Hint, solve_iter, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("crane"), Word::new("sight")];
+/// let hints = [Hint::Black, Hint::Black, Hint::Black, Hint::Yellow, Hint::Green];
+/// let history = [(Word::new("unlit"), hints)];
+/// let mut solutions = solve_iter(&words, &history);
+///
+/// assert_eq!(Some(&words[0]), solutions.next());
+/// assert_eq!(2, solutions.count());
+/// ```
+pub fn solve_iter<'a, 'g, H, I>(
     words: &'a [Word],
-    guesses_and_hints: &[(Word, [Hint; Word::SIZE])],
-) -> Vec<&'a Word> {
-    let constraints = guesses_and_hints
+    guesses_and_hints: I,
+) -> impl Iterator<Item = &'a Word>
+where
+    H: AsRef<[Hint]> + 'g,
+    I: IntoIterator<Item = &'g (Word, H)>,
+{
+    let constraints = constraints_of(guesses_and_hints).collect();
+
+    allowed_by(words, constraints)
+}
+
+/// Filters out the words using the guesses and hints like [`solve`], but returns an error naming
+/// the conflicting constraints when the hints contradict each other, e.g. when two guesses require
+/// different letters at the same position. This tells mistyped hints apart from hints which no
+/// word of the dictionary happens to match.
+///
+/// # Panics
+///
+/// Panics if there is not exactly one hint per character of a guess, or if the guesses are not all
+/// as long.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Hint, solve_checked, Word};
+/// let words = [Word::new("fight"), Word::new("light"), Word::new("sight")];
+/// let greens = [Hint::Green; 5];
+///
+/// assert_eq!(vec![&words[1]], solve_checked(&words, &[(Word::new("light"), greens)]).unwrap());
+/// assert_eq!(
+///     Err("contradictory hints: position 0 must be 'l' but position 0 must be 's'".to_string()),
+///     solve_checked(&words, &[(Word::new("light"), greens), (Word::new("sight"), greens)])
+/// );
+/// ```
+pub fn solve_checked<'a, H: AsRef<[Hint]>>(
+    words: &'a [Word],
+    guesses_and_hints: &[(Word, H)],
+) -> Result<Vec<&'a Word>, String> {
+    let constraints = constraints_of(guesses_and_hints).collect::<Vec<Constraints>>();
+
+    if !constraints.is_empty() {
+        if let Some((first, second)) = Constraints::merge(&constraints).contradiction() {
+            return Err(format!("contradictory hints: {} but {}", first, second));
+        }
+    }
+
+    Ok(solve(words, guesses_and_hints))
+}
+
+/// Filters out the possible answers using the guesses and hints, like [`solve_checked`], when the
+/// words which may be guessed are not the same as the words which may be the solution, e.g. a
+/// large list of allowed guesses and a smaller curated list of answers.
+///
+/// Returns an error if a guess is neither an allowed guess nor a possible answer, or if the hints
+/// contradict each other.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{solve_with_answers, Hint, Word};
+/// let answers = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+/// let allowed = [Word::new("aahed"), Word::new("unlit")];
+/// let hints = [Hint::Black, Hint::Black, Hint::Black, Hint::Yellow, Hint::Green];
+///
+/// let solutions = solve_with_answers(&[(Word::new("unlit"), hints)], &answers, &allowed).unwrap();
+/// assert_eq!(vec![&answers[0], &answers[1]], solutions);
+///
+/// assert!(solve_with_answers(&[(Word::new("tight"), hints)], &answers, &allowed).is_err());
+/// ```
+pub fn solve_with_answers<'a, H: AsRef<[Hint]>>(
+    guesses_and_hints: &[(Word, H)],
+    answers: &'a [Word],
+    allowed: &[Word],
+) -> Result<Vec<&'a Word>, String> {
+    if let Some((guess, _)) = guesses_and_hints
         .iter()
-        .map(|(guess, hints)| {
-            Constraints::from_pattern(&Pattern::from_guess_and_hints(guess, hints))
-        })
-        .collect::<Vec<Constraints>>();
+        .find(|(guess, _)| !allowed.contains(guess) && !answers.contains(guess))
+    {
+        return Err(format!("{} is not an allowed guess", guess));
+    }
 
-    words
+    solve_checked(answers, guesses_and_hints)
+}
+
+/// Filters out the words using the guesses and hints like [`solve`], each word having a weight
+/// such as its frequency, then sorts the remaining words by descending weight so that the likeliest
+/// solutions come first. Words with equal weights stay in the order of `words`.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{solve_weighted, Hint, Word};
+/// let words = [(Word::new("fight"), 12.0), (Word::new("might"), 480.0), (Word::new("crane"), 30.0)];
+/// let hints = [Hint::Black, Hint::Black, Hint::Black, Hint::Yellow, Hint::Green];
+/// let solutions = solve_weighted(&words, &[(Word::new("unlit"), hints)]);
+///
+/// assert_eq!(vec![(&Word::new("might"), 480.0), (&Word::new("fight"), 12.0)], solutions);
+/// ```
+#[must_use]
+pub fn solve_weighted<'a, H: AsRef<[Hint]>>(
+    words: &'a [(Word, f64)],
+    guesses_and_hints: &[(Word, H)],
+) -> Vec<(&'a Word, f64)> {
+    let constraints = constraints_of(guesses_and_hints).collect::<Vec<Constraints>>();
+    let mut solutions = words
+        .iter()
+        .filter(|(word, _)| constraints.iter().all(|pattern| pattern.matches(word)))
+        .map(|(word, weight)| (word, *weight))
+        .collect::<Vec<_>>();
+
+    solutions.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+    solutions
+}
+
+/// Finds the words which violate at most `max_violations` of the constraints imposed by the guesses
+/// and hints, sorted by fewest violations, then in the order of `words`.
+///
+/// This helps recovering from a mistyped hint: when [`solve`] finds no word, the intended solution
+/// usually violates a single constraint.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Hint, nearest_candidates, solve, Word};
+/// let words = [Word::new("plume"), Word::new("prime"), Word::new("torch")];
+/// let guess = Word::new("coupe");
+/// // The last hint should have been green.
+/// let hints = [Hint::Black, Hint::Black, Hint::Black, Hint::Yellow, Hint::Yellow];
+///
+/// assert!(solve(&words, &[(guess.clone(), hints)]).is_empty());
+/// assert_eq!(vec![(&Word::new("prime"), 1)], nearest_candidates(&words, &[(guess, hints)], 1));
+/// ```
+#[must_use]
+pub fn nearest_candidates<'a, H: AsRef<[Hint]>>(
+    words: &'a [Word],
+    guesses_and_hints: &[(Word, H)],
+    max_violations: usize,
+) -> Vec<(&'a Word, usize)> {
+    let constraints = constraints_of(guesses_and_hints).collect::<Vec<Constraints>>();
+
+    let mut candidates = words
         .iter()
-        .filter(|word| constraints.iter().all(|pattern| pattern.matches(word)))
+        .map(|word| {
+            let violations = constraints
+                .iter()
+                .map(|constraints| constraints.violations(word))
+                .sum::<usize>();
+            (word, violations)
+        })
+        .filter(|(_, violations)| *violations <= max_violations)
+        .collect::<Vec<_>>();
+
+    candidates.sort_by_key(|(_, violations)| *violations);
+    candidates
+}
+
+/// Tells, for each guess and its hints, whether the word is still possible afterwards, and if not,
+/// why the hints rule it out, as given by [`Constraints::reasons`]. Each guess is considered on its
+/// own, by its index in `guesses_and_hints`. Reasons are empty for the guesses the word passes.
+///
+/// # Panics
+///
+/// Panics if a guess does not have exactly one hint per character.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{explain, Hint, Word};
+/// let crane = Word::new("crane");
+/// let hints = [Hint::Black, Hint::Black, Hint::Black, Hint::Black, Hint::Yellow];
+///
+/// assert_eq!(vec![(0, true, String::new())], explain(&Word::new("dwelt"), &[(crane.clone(), hints)]));
+/// assert_eq!(
+///     vec![(0, false, "missing required 'e'".to_string())],
+///     explain(&Word::new("might"), &[(crane, hints)])
+/// );
+/// ```
+#[must_use]
+pub fn explain<H: AsRef<[Hint]>>(
+    word: &Word,
+    guesses_and_hints: &[(Word, H)],
+) -> Vec<(usize, bool, String)> {
+    constraints_of(guesses_and_hints)
+        .enumerate()
+        .map(|(i, constraints)| {
+            let reasons = constraints.reasons(word);
+            (i, reasons.is_empty(), reasons.join(", "))
+        })
         .collect()
 }
 
+/// Filters out the words using the guesses and hints like [`solve`], then lazily yields the
+/// remaining words from the highest to the lowest score.
+///
+/// Each remaining word is scored once, with `score` receiving the word and every remaining word,
+/// but the words are only ordered as they are yielded, so that taking the first few is cheap.
+/// Words with equal scores are yielded in the order of `words`.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Hint, solve_ranked_iter, Word};
+/// let words = [Word::new("apple"), Word::new("prime"), Word::new("torch")];
+/// let guess = Word::new("coupe");
+/// let hints = [Hint::Black, Hint::Black, Hint::Black, Hint::Yellow, Hint::Green];
+/// let count_p = |word: &Word, _: &[&Word]| word.count('p') as f64;
+/// let mut ranked = solve_ranked_iter(&words, &[(guess, hints)], count_p);
+///
+/// assert_eq!(Some((&Word::new("apple"), 2.0)), ranked.next());
+/// assert_eq!(Some((&Word::new("prime"), 1.0)), ranked.next());
+/// assert_eq!(None, ranked.next());
+/// ```
+pub fn solve_ranked_iter<'a, H, F>(
+    words: &'a [Word],
+    guesses_and_hints: &[(Word, H)],
+    score: F,
+) -> impl Iterator<Item = (&'a Word, f64)>
+where
+    H: AsRef<[Hint]>,
+    F: Fn(&Word, &[&Word]) -> f64,
+{
+    let candidates = solve(words, guesses_and_hints);
+    let mut heap = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, word)| Ranked {
+            word,
+            score: score(word, &candidates),
+            index,
+        })
+        .collect::<BinaryHeap<Ranked>>();
+
+    std::iter::from_fn(move || heap.pop().map(|ranked| (ranked.word, ranked.score)))
+}
+
+struct Ranked<'a> {
+    word: &'a Word,
+    score: f64,
+    index: usize,
+}
+
+impl PartialEq for Ranked<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked<'_> {}
+
+impl PartialOrd for Ranked<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{filter, matches, solve, Hint, Word};
+    use proptest::prelude::*;
+
+    use crate::{
+        filter, filter_excluding, filter_iter, filter_template, load_default_words, matches,
+        matches_all, matches_any, matches_ranked, nearest_candidates, solve, solve_iter,
+        solve_ranked_iter, solve_trace, solve_weighted, solve_with_answers, Hint, Pattern, Word,
+        WordsError,
+    };
 
     #[test]
     fn given_guess_is_solution_when_filter_then_no_other_words_can_be_the_solution() {
@@ -162,6 +814,90 @@ mod tests {
         assert_eq!(vec![&Word::new("prime"), &Word::new("phone")], matches);
     }
 
+    #[test]
+    fn given_scorer_when_matches_ranked_then_matches_are_sorted_by_descending_score() {
+        let words = ["apple", "prime", "plume", "phone", "torch", "watch"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let hints = [
+            Hint::Yellow,
+            Hint::Black,
+            Hint::Black,
+            Hint::Black,
+            Hint::Green,
+        ];
+        let scorer = |word: &Word| word.count('h') as f64;
+        let matches = matches_ranked(&words, &Word::new("apple"), &hints, &scorer);
+
+        assert_eq!(vec![&Word::new("phone"), &Word::new("prime")], matches);
+    }
+
+    #[test]
+    fn given_lazily_generated_guesses_when_solve_then_same_as_slice() {
+        let words = ["fight", "might", "light", "crane", "plumb"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let solution = Word::new("light");
+        let history = ["crane", "fight"]
+            .into_iter()
+            .map(|guess| {
+                let guess = Word::new(guess);
+                let pattern = Pattern::from_solution_and_guess(&solution, &guess);
+                (guess, pattern.hints)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(solve(&words, &history), solve(&words, history.iter().rev()));
+        assert_eq!(
+            filter(&words, &solution, &[Word::new("crane"), Word::new("fight")]),
+            filter(&words, &solution, history.iter().map(|(guess, _)| guess))
+        );
+        assert_eq!(
+            filter(&words, &solution, history.iter().map(|(guess, _)| guess)),
+            filter_iter(&words, &solution, history.iter().map(|(guess, _)| guess))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&words[2]],
+            filter_excluding(
+                &words,
+                &solution,
+                history.iter().map(|(guess, _)| guess),
+                &[Word::new("might")]
+            )
+        );
+        assert_eq!(
+            solve(&words, &history),
+            solve_iter(&words, history.iter().rev()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            solve_trace(&words, &history),
+            solve_trace(&words, history.iter().take(2))
+        );
+    }
+
+    #[test]
+    fn given_well_chosen_guesses_when_solve_trace_then_counts_strictly_decrease() {
+        let words = load_default_words();
+        let solution = Word::new("mourn");
+        let history = ["soare", "clint", "mourn"]
+            .into_iter()
+            .map(|guess| {
+                let pattern = Pattern::from_solution_and_guess(&solution, &Word::new(guess));
+                (pattern.guess, pattern.hints)
+            })
+            .collect::<Vec<_>>();
+        let trace = solve_trace(&words, &history);
+
+        assert_eq!(3, trace.len());
+        assert!(trace[0] < words.len());
+        assert!(trace.windows(2).all(|counts| counts[0] > counts[1]));
+        assert_eq!(1, trace[2]);
+        assert_eq!(solve(&words, &history[..2]).len(), trace[1]);
+    }
+
     #[test]
     fn given_guess_and_hints_when_solve_then_filter_out_non_possible_words() {
         let words = ["apple", "prime", "plume", "torch", "watch", "soles"]
@@ -180,4 +916,357 @@ mod tests {
 
         assert_eq!(vec![&Word::new("apple"), &Word::new("prime")], solutions);
     }
+
+    #[test]
+    fn given_a_hint_is_wrong_when_nearest_candidates_then_solution_has_one_violation() {
+        let words = ["apple", "prime", "plume", "torch", "watch", "soles"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let guess = Word::new("coupe");
+        let hints = [
+            Hint::Black,
+            Hint::Black,
+            Hint::Black,
+            Hint::Yellow,
+            Hint::Yellow,
+        ];
+        let guesses_and_hints = [(guess, hints)];
+
+        assert!(solve(&words, &guesses_and_hints).is_empty());
+        assert_eq!(
+            vec![(&Word::new("apple"), 1), (&Word::new("prime"), 1)],
+            nearest_candidates(&words, &guesses_and_hints, 1)
+        );
+    }
+
+    #[test]
+    fn given_no_violations_allowed_when_nearest_candidates_then_return_solve_results() {
+        let words = ["apple", "prime", "plume", "torch", "watch", "soles"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let guess = Word::new("coupe");
+        let hints = [
+            Hint::Black,
+            Hint::Black,
+            Hint::Black,
+            Hint::Yellow,
+            Hint::Green,
+        ];
+        let nearest = nearest_candidates(&words, &[(guess.clone(), hints)], 0)
+            .into_iter()
+            .map(|(word, _)| word)
+            .collect::<Vec<&Word>>();
+
+        assert_eq!(solve(&words, &[(guess, hints)]), nearest);
+    }
+
+    #[test]
+    fn when_solve_ranked_iter_then_first_word_has_the_highest_score() {
+        let words = ["apple", "prime", "plume", "torch", "watch", "soles"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let guess = Word::new("coupe");
+        let hints = [
+            Hint::Black,
+            Hint::Black,
+            Hint::Black,
+            Hint::Yellow,
+            Hint::Green,
+        ];
+        let mut ranked =
+            solve_ranked_iter(&words, &[(guess, hints)], |word, _| word.count('r') as f64);
+
+        assert_eq!(Some((&Word::new("prime"), 1.0)), ranked.next());
+    }
+
+    #[test]
+    fn given_equal_scores_when_solve_ranked_iter_then_words_are_in_dictionary_order() {
+        let words = ["apple", "prime", "plume", "torch", "watch", "soles"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let ranked =
+            solve_ranked_iter::<Vec<Hint>, _>(&words, &[], |_, candidates| candidates.len() as f64)
+                .map(|(word, _)| word)
+                .collect::<Vec<&Word>>();
+
+        assert_eq!(words.iter().collect::<Vec<&Word>>(), ranked);
+    }
+
+    #[test]
+    fn given_words_of_another_length_when_filter_then_only_words_as_long_as_solution_remain() {
+        let words = ["rust", "bust", "trust", "must"]
+            .into_iter()
+            .map(|word| Word::with_length(word, word.len()).unwrap())
+            .collect::<Vec<Word>>();
+        let solution = Word::with_length("must", 4).unwrap();
+        let guess = Word::with_length("rust", 4).unwrap();
+        let solutions = filter(&words, &solution, &[guess]);
+
+        assert_eq!(vec![&words[1], &words[3]], solutions);
+    }
+
+    #[test]
+    fn given_words_of_another_length_when_solve_then_only_words_as_long_as_guesses_remain() {
+        let words = ["rust", "trust", "crust"]
+            .into_iter()
+            .map(|word| Word::with_length(word, word.len()).unwrap())
+            .collect::<Vec<Word>>();
+        let guess = Word::with_length("rust", 4).unwrap();
+        let hints = [Hint::Green; 4];
+
+        assert_eq!(vec![&words[0]], solve(&words, &[(guess, hints)]));
+    }
+
+    #[test]
+    fn given_no_guesses_when_filter_then_only_words_as_long_as_solution_remain() {
+        let words = [Word::new("crane"), Word::with_length("pear", 4).unwrap()];
+        let solution = Word::new("crane");
+        let guesses: [Word; 0] = [];
+
+        assert_eq!(vec![&words[0]], filter(&words, &solution, &guesses));
+        assert_eq!(
+            vec![&words[0]],
+            filter_iter(&words, &solution, &guesses).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&words[0]],
+            filter_excluding(&words, &solution, &guesses, &[])
+        );
+    }
+
+    #[test]
+    fn given_no_guesses_when_solve_then_every_word_remains() {
+        let words = [Word::new("crane"), Word::with_length("pear", 4).unwrap()];
+        let guesses_and_hints: [(Word, [Hint; 5]); 0] = [];
+
+        assert_eq!(
+            vec![&words[0], &words[1]],
+            solve(&words, &guesses_and_hints)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "different lengths")]
+    fn given_guess_is_not_as_long_as_solution_when_filter_then_panic() {
+        let words = [Word::new("apple")];
+
+        let _ = filter(
+            &words,
+            &Word::new("apple"),
+            &[Word::with_length("pear", 4).unwrap()],
+        );
+    }
+
+    #[test]
+    fn given_answers_and_allowed_guesses_when_solve_with_answers_then_return_only_answers() {
+        let answers = [Word::new("fight"), Word::new("sight"), Word::new("crane")];
+        let allowed = [Word::new("light"), Word::new("might"), Word::new("unlit")];
+        let hints = [
+            Hint::Black,
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+        ];
+        let solutions = solve_with_answers(&[(Word::new("light"), hints)], &answers, &allowed);
+
+        assert_eq!(Ok(vec![&answers[0], &answers[1]]), solutions);
+    }
+
+    #[test]
+    fn given_guess_is_an_answer_when_solve_with_answers_then_guess_is_allowed() {
+        let answers = [Word::new("fight"), Word::new("crane")];
+        let solutions = solve_with_answers(
+            &[(Word::new("fight"), [Hint::Green; crate::SIZE])],
+            &answers,
+            &[],
+        );
+
+        assert_eq!(Ok(vec![&answers[0]]), solutions);
+    }
+
+    #[test]
+    fn given_guess_is_not_allowed_when_solve_with_answers_then_return_error() {
+        let answers = [Word::new("fight")];
+        let allowed = [Word::new("light")];
+        let solutions = solve_with_answers(
+            &[(Word::new("xxxxx"), [Hint::Black; crate::SIZE])],
+            &answers,
+            &allowed,
+        );
+
+        assert!(solutions.is_err());
+    }
+
+    #[test]
+    fn given_weighted_words_when_solve_weighted_then_sort_solutions_by_descending_weight() {
+        let words = [
+            (Word::new("fight"), 1.0),
+            (Word::new("light"), 5.0),
+            (Word::new("crane"), 9.0),
+            (Word::new("might"), 5.0),
+            (Word::new("sight"), 2.0),
+        ];
+        let hints = [
+            Hint::Black,
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+        ];
+        let solutions = solve_weighted(&words, &[(Word::new("tight"), hints)]);
+
+        assert_eq!(
+            vec![
+                (&Word::new("light"), 5.0),
+                (&Word::new("might"), 5.0),
+                (&Word::new("sight"), 2.0),
+                (&Word::new("fight"), 1.0),
+            ],
+            solutions
+        );
+    }
+
+    #[test]
+    fn given_hints_as_vec_when_matches_then_match_like_array() {
+        let words = [Word::new("cargo"), Word::new("babel"), Word::new("orbit")];
+        let hints = "bgbbb"
+            .chars()
+            .map(|c| if c == 'g' { Hint::Green } else { Hint::Black })
+            .collect::<Vec<Hint>>();
+
+        assert_eq!(
+            matches(
+                &words,
+                &Word::new("cargo"),
+                &[
+                    Hint::Black,
+                    Hint::Green,
+                    Hint::Black,
+                    Hint::Black,
+                    Hint::Black
+                ]
+            ),
+            matches(&words, &Word::new("cargo"), &hints)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "one hint per character")]
+    fn given_too_few_hints_when_matches_then_panic() {
+        let _ = matches(
+            &[Word::new("cargo")],
+            &Word::new("cargo"),
+            &[Hint::Green; 4],
+        );
+    }
+
+    #[test]
+    fn given_several_solutions_when_matches_any_and_matches_all_then_return_union_and_intersection()
+    {
+        let words = ["plumb", "dowry", "stomp", "trace", "wimpy"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let solutions = [Word::new("fight"), Word::new("crane")];
+        let hints = [Hint::Black; 5];
+
+        let any = matches_any(&words, &solutions, &hints);
+        let all = matches_all(&words, &solutions, &hints);
+
+        for word in &words {
+            let first = matches(&words, &solutions[0], &hints).contains(&word);
+            let second = matches(&words, &solutions[1], &hints).contains(&word);
+
+            assert_eq!(first || second, any.contains(&word), "{}", word);
+            assert_eq!(first && second, all.contains(&word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn given_no_solutions_when_matches_any_and_matches_all_then_match_none_or_every_word() {
+        let words = [Word::new("plumb"), Word::new("dowry")];
+
+        assert!(matches_any(&words, &[], &[Hint::Black; 5]).is_empty());
+        assert_eq!(2, matches_all(&words, &[], &[Hint::Black; 5]).len());
+    }
+
+    #[test]
+    fn given_excluded_words_when_filter_excluding_then_remove_them() {
+        let words = ["fight", "might", "light", "sight"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+        let remaining = filter_excluding(
+            &words,
+            &Word::new("sight"),
+            &[Word::new("crane")],
+            &[Word::new("Light"), Word::new("sight"), Word::new("plumb")],
+        );
+
+        assert_eq!(vec![&words[0], &words[1]], remaining);
+    }
+
+    #[test]
+    fn given_template_with_wildcards_when_filter_template_then_absent_letters_are_not_implied() {
+        let words = ["cable", "caret", "cacao", "plumb"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+
+        assert_eq!(
+            Ok(vec![&words[0], &words[1], &words[2]]),
+            filter_template(&words, "Ca___")
+        );
+        assert_eq!(
+            Ok(4),
+            filter_template(&words, "_____").map(|words| words.len())
+        );
+    }
+
+    #[test]
+    fn given_invalid_template_when_filter_template_then_return_error() {
+        let words = [Word::new("crane")];
+
+        assert_eq!(
+            Err(WordsError::WrongLength {
+                expected: 5,
+                actual: 6
+            }),
+            filter_template(&words, "cr____")
+        );
+        assert_eq!(
+            Err(WordsError::NonAlphabetic),
+            filter_template(&words, "cr?__")
+        );
+    }
+
+    proptest! {
+        /// The hints a guess produces never rule out the solution, and the guess is the word which
+        /// produces them. Repeated letters are likely, since the letters are drawn from a small
+        /// set.
+        #[test]
+        fn solution_survives_its_own_hints(solution in "[a-f]{5}", guess in "[a-f]{5}") {
+            let solution = Word::new(&solution);
+            let guess = Word::new(&guess);
+            let hints = Pattern::from_solution_and_guess(&solution, &guess).hints;
+            let words = [solution.clone(), guess.clone()];
+
+            prop_assert!(solve(&words, &[(guess.clone(), &hints)]).contains(&&solution));
+            prop_assert!(matches(&words, &solution, &hints).contains(&&guess));
+        }
+
+        #[test]
+        fn solution_survives_hints_of_any_letters(solution in "[a-z]{5}", guess in "[a-z]{5}") {
+            let solution = Word::new(&solution);
+            let guess = Word::new(&guess);
+            let hints = Pattern::from_solution_and_guess(&solution, &guess).hints;
+
+            prop_assert!(solve(std::slice::from_ref(&solution), &[(guess, hints)]).contains(&&solution));
+        }
+    }
 }
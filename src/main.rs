@@ -1,14 +1,29 @@
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
 use std::ffi::{OsStr, OsString};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::hash::BuildHasher;
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 
-use clap::{Parser, Subcommand};
+use clap::error::{ContextKind, ContextValue};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 
-use wools::{load_default_words, Hint, Word};
+use wools::{
+    best_guess, best_openers, expected_remaining, load_default_words, load_weighted_words_iter,
+    rank_guesses, rank_minimax, DecisionTree, Game, GameStatus, Hint, Pattern, Word, WordList,
+    WordsError, DEFAULT_FREQUENCY,
+};
 
 const DEFAULT_WORDLE_URL: &str = "https://www.nytimes.com/games/wordle/index.html";
+const SOLUTION_VAR: &str = "WOOLS_SOLUTION";
+const NO_COLOR_VAR: &str = "NO_COLOR";
+const SUGGESTION_COUNT: usize = 3;
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+#[cfg(not(feature = "serde"))]
+const OPENERS_CACHE_UNSUPPORTED: &str = "caching openers requires the serde feature";
 
 #[derive(Parser)]
 #[clap(version, about)]
@@ -17,10 +32,60 @@ struct Opt {
     #[clap(short, long, value_parser)]
     dictionary: Option<PathBuf>,
 
+    /// Sets the path to the list of words which may be the solution, instead of the dictionary
+    #[clap(long, value_parser)]
+    answers: Option<PathBuf>,
+
+    /// Sets the path to the list of words which may be guessed, instead of the dictionary
+    #[clap(long, value_parser)]
+    allowed: Option<PathBuf>,
+
+    /// Sets the format in which the words are printed
+    #[clap(short, long, value_enum, global = true, default_value_t = Format::Plain)]
+    format: Format,
+
+    /// Sorts the words alphabetically, instead of keeping the order of the dictionary or, for
+    /// `solve`, of the frequencies
+    #[clap(long, alias = "sort", global = true)]
+    sorted: bool,
+
+    /// Prints at most this many words, telling how many more there are on stderr, 0 being no
+    /// limit
+    #[clap(long, global = true, default_value_t = 0)]
+    limit: usize,
+
     #[clap(subcommand)]
     command: Command,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// One word per line
+    Plain,
+    /// A JSON array of words, or an object with the number of words left for `solve`
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ColorMode {
+    /// Colors when printing to a terminal, unless NO_COLOR is set
+    Auto,
+    /// Always colors
+    Always,
+    /// Never colors
+    Never,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Metric {
+    /// The information the hints give on average, in bits, the higher the better
+    Entropy,
+    /// The number of words left by the worst hints, the lower the better
+    Minimax,
+    /// The number of words left on average, the lower the better
+    Average,
+}
+
 #[derive(Subcommand)]
 enum Command {
     /// Filters the list of words using the guesses
@@ -31,24 +96,112 @@ enum Command {
         /// Sets the five-letter guesses to use to filter
         #[clap()]
         guesses: Vec<Word>,
+        /// Sets the words to leave out of the result, separated by commas
+        #[clap(long, value_delimiter = ',')]
+        exclude: Vec<Word>,
     },
     /// Finds the word matching the pattern knowing the solution
     Match {
         /// Sets the five-letter word as the solution
         #[clap()]
         solution: Word,
-        /// Sets the pattern to match
+        /// Sets the pattern to match, made of g, y and b, or of 2, 1 and 0, for green, yellow and
+        /// black hints, where . and - are also black
         #[clap(name = "PATTERN", value_parser = parse_hints)]
         hints: [Hint; Word::SIZE],
+        /// Sets when to color the words like in the game, auto being when printing plain words to
+        /// a terminal and NO_COLOR is not set
+        #[clap(long, value_enum, default_value_t = ColorMode::Auto)]
+        color: ColorMode,
     },
     /// Finds the words that may be the solution
     Solve {
-        /// Sets the guess and its hints, separated by a comma
+        /// Reads more guesses and their hints from stdin, one per line, until one word remains
+        #[clap(short, long)]
+        interactive: bool,
+        /// Sets the words to leave out of the result, separated by commas
+        #[clap(long, value_delimiter = ',')]
+        exclude: Vec<Word>,
+        /// Reads guesses and their hints from a file, or from stdin if -, one per line, before
+        /// the ones given as arguments
+        #[clap(long, value_parser)]
+        from_file: Option<PathBuf>,
+        /// Sets the guess and its hints, separated by a comma, the hints being either letters
+        /// (g, y, b), digits (2, 1, 0) or a copied row of emojis
+        #[clap(name = "GUESS", value_parser = parse_guess_and_hints)]
+        guesses_and_hints: Vec<(Word, [Hint; Word::SIZE])>,
+    },
+    /// Counts the words that may be the solution
+    Count {
+        /// Sets the guess and its hints, separated by a comma, the hints being either letters
+        /// (g, y, b), digits (2, 1, 0) or a copied row of emojis
+        #[clap(name = "GUESS", value_parser = parse_guess_and_hints)]
+        guesses_and_hints: Vec<(Word, [Hint; Word::SIZE])>,
+    },
+    /// Suggests the best next guesses with their scores
+    Suggest {
+        /// Sets the guess and its hints, separated by a comma, the hints being either letters
+        /// (g, y, b), digits (2, 1, 0) or a copied row of emojis
+        #[clap(name = "GUESS", value_parser = parse_guess_and_hints)]
+        guesses_and_hints: Vec<(Word, [Hint; Word::SIZE])>,
+        /// Sets the number of guesses to suggest
+        #[clap(long, default_value_t = 10)]
+        top: usize,
+        /// Sets how the guesses are scored
+        #[clap(long, value_enum, default_value_t = Metric::Entropy)]
+        metric: Metric,
+    },
+    /// Tells, for each guess, whether the word is still possible and if not, why
+    Explain {
+        /// Sets the five-letter word to check against the guesses
+        #[clap()]
+        word: Word,
+        /// Sets the guess and its hints, separated by a comma, the hints being either letters
+        /// (g, y, b), digits (2, 1, 0) or a copied row of emojis
         #[clap(name = "GUESS", value_parser = parse_guess_and_hints)]
         guesses_and_hints: Vec<(Word, [Hint; Word::SIZE])>,
     },
     /// Displays the list of valid, normalized words from the dictionary.
-    Dict,
+    Dict {
+        /// Prints statistics on the words instead of the words: how many there are, how many
+        /// repeat a letter, and how often each letter appears
+        #[clap(long)]
+        stats: bool,
+    },
+    /// Plays a game against a random word from the dictionary, reading guesses from stdin.
+    ///
+    /// The solution may be set with the WOOLS_SOLUTION environment variable instead.
+    Practice {
+        /// Sets the number of guesses allowed to find the solution
+        #[clap(short, long, default_value_t = Game::DEFAULT_MAX_GUESSES)]
+        tries: usize,
+        /// Rejects the guesses which are not in the dictionary
+        #[clap(long)]
+        strict: bool,
+        /// Reports how long each guess took once the game is over
+        #[clap(long)]
+        timed: bool,
+    },
+    /// Suggests the best first guesses with their scores, which takes a while for a whole
+    /// dictionary unless they were cached.
+    Openers {
+        /// Sets the number of guesses to suggest
+        #[clap(long, default_value_t = 10)]
+        top: usize,
+        /// Sets the path to a file caching the best first guesses, read if it was written for the
+        /// same dictionary, and written otherwise. Requires the serde feature
+        #[clap(long, value_parser)]
+        cache: Option<PathBuf>,
+    },
+    /// Builds the decision tree of every game starting with the opener and writes it to a file.
+    ExportTree {
+        /// Sets the five-letter word to open every game with
+        #[clap(long)]
+        opener: Word,
+        /// Sets the path to the file to write the tree to
+        #[clap(short, long, value_parser)]
+        output: PathBuf,
+    },
     /// Opens Wordle in the default browser.
     Open {
         #[clap(short, long, default_value = DEFAULT_WORDLE_URL)]
@@ -57,112 +210,928 @@ enum Command {
 }
 
 fn main() -> Result<(), String> {
-    let opt: Opt = Opt::parse();
-    let words = opt
-        .dictionary
-        .map(load_words)
-        .unwrap_or_else(|| Ok(load_default_words()))?;
+    let opt = Opt::try_parse().unwrap_or_else(|err| with_closest_words(err).exit());
+    let words = opt.dictionary.map(load_words).unwrap_or_else(|| {
+        Ok(load_default_words()
+            .into_iter()
+            .map(|word| (word, DEFAULT_FREQUENCY))
+            .collect())
+    })?;
+
+    let answers = opt.answers.map(load_words).transpose()?;
+    let allowed = opt.allowed.map(load_words).transpose()?;
+    // Without both lists, the dictionary stands for the missing one. Guesses are only checked when
+    // a list of allowed guesses or answers is given.
+    let (mut words, allowed) = match (answers, allowed) {
+        (None, None) => (words, None),
+        (Some(answers), None) => (answers, Some(words)),
+        (None, Some(allowed)) => (words, Some(allowed)),
+        (Some(answers), Some(allowed)) => (answers, Some(allowed)),
+    };
+    let allowed = allowed.map(unweighted);
+
+    // The commands keep the order of the words, so sorting them sorts every result.
+    if opt.sorted {
+        words.sort_by(|(first, _), (second, _)| first.cmp(second));
+    }
 
     match opt.command {
-        Command::Filter { solution, guesses } => filter(words, solution, guesses),
-        Command::Match { solution, hints } => matches(words, solution, hints),
-        Command::Solve { guesses_and_hints } => solve(words, guesses_and_hints),
-        Command::Dict => dict(words),
+        Command::Filter {
+            solution,
+            guesses,
+            exclude,
+        } => filter(
+            unweighted(words),
+            allowed,
+            solution,
+            guesses,
+            exclude,
+            opt.format,
+            opt.limit,
+        ),
+        Command::Match {
+            solution,
+            hints,
+            color,
+        } => matches(
+            unweighted(words),
+            solution,
+            hints,
+            opt.format,
+            opt.limit,
+            color,
+        ),
+        Command::Solve {
+            interactive,
+            exclude,
+            from_file,
+            guesses_and_hints,
+        } => {
+            let mut words = words;
+            words.retain(|(word, _)| !exclude.contains(word));
+            let guesses_and_hints = match from_file {
+                Some(path) => {
+                    let mut history = read_guesses_and_hints(&path)?;
+                    history.extend(guesses_and_hints);
+                    history
+                }
+                None => guesses_and_hints,
+            };
+
+            if interactive {
+                solve_interactively(unweighted(words), guesses_and_hints)
+            } else {
+                solve(
+                    words,
+                    allowed,
+                    guesses_and_hints,
+                    opt.format,
+                    opt.limit,
+                    opt.sorted,
+                )
+            }
+        }
+        Command::Count { guesses_and_hints } => {
+            count(unweighted(words), allowed, guesses_and_hints)
+        }
+        Command::Suggest {
+            guesses_and_hints,
+            top,
+            metric,
+        } => suggest(unweighted(words), allowed, guesses_and_hints, top, metric),
+        Command::Explain {
+            word,
+            guesses_and_hints,
+        } => {
+            explain(word, guesses_and_hints);
+            Ok(())
+        }
+        Command::Dict { stats } => dict(unweighted(words), stats, opt.format, opt.limit),
+        Command::Practice {
+            tries,
+            strict,
+            timed,
+        } => practice(unweighted(words), tries, strict, timed),
+        Command::Openers { top, cache } => openers(unweighted(words), top, cache),
+        Command::ExportTree { opener, output } => export_tree(unweighted(words), opener, output),
         Command::Open { url } => open(url),
     }
 }
 
-fn parse_hints(s: &str) -> Result<[Hint; Word::SIZE], String> {
-    let s = s.to_lowercase();
+/// Adds the dictionary words closest to the word of an invalid argument to the error, when the word
+/// only has the wrong length. The dictionary is read from the arguments which could be parsed.
+fn with_closest_words(mut err: clap::Error) -> clap::Error {
+    let Some(ContextValue::String(value)) = err.get(ContextKind::InvalidValue) else {
+        return err;
+    };
+    let typed = value
+        .split(',')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    if !matches!(Word::from_str(&typed), Err(WordsError::WrongLength { .. })) {
+        return err;
+    }
+
+    let dictionary = Opt::command()
+        .ignore_errors(true)
+        .try_get_matches()
+        .ok()
+        .and_then(|matches| matches.get_one::<PathBuf>("dictionary").cloned());
+    let words = match dictionary {
+        Some(path) => load_words(path).map(unweighted).unwrap_or_default(),
+        None => load_default_words(),
+    };
+    let closest = closest_words(&typed, &words);
+
+    if !closest.is_empty() {
+        err.insert(
+            ContextKind::SuggestedValue,
+            ContextValue::Strings(closest.iter().map(ToString::to_string).collect()),
+        );
+    }
+
+    err
+}
+
+/// Suggests the dictionary words closest to the typed word when the word only has the wrong
+/// length, e.g. "word is not 5-character long, did you mean crane or crate?".
+fn suggest_closest_words(err: String, typed: &str, words: &[Word]) -> String {
+    if !matches!(Word::from_str(typed), Err(WordsError::WrongLength { .. })) {
+        return err;
+    }
+
+    match closest_words(typed, words).as_slice() {
+        [] => err,
+        [word] => format!("{}, did you mean {}?", err, word),
+        [words @ .., last] => {
+            let words = words.iter().map(ToString::to_string).collect::<Vec<_>>();
+            format!("{}, did you mean {} or {}?", err, words.join(", "), last)
+        }
+    }
+}
+
+/// Finds at most [`SUGGESTION_COUNT`] words at an edit distance of at most
+/// [`MAX_SUGGESTION_DISTANCE`] from the typed word, the closest first, then in dictionary order.
+fn closest_words<'a>(typed: &str, words: &'a [Word]) -> Vec<&'a Word> {
+    let typed = typed.to_lowercase();
+    let mut closest = words
+        .iter()
+        .map(|word| (word, edit_distance(&typed, word.as_str())))
+        .filter(|(_, distance)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .collect::<Vec<_>>();
+
+    closest.sort_by_key(|(_, distance)| *distance);
+    closest
+        .into_iter()
+        .take(SUGGESTION_COUNT)
+        .map(|(word, _)| word)
+        .collect()
+}
+
+/// Counts the characters to insert, delete or substitute to turn one string into the other, i.e.
+/// the Levenshtein distance between the strings.
+fn edit_distance(first: &str, second: &str) -> usize {
+    let second = second.chars().collect::<Vec<char>>();
+    let mut previous = (0..=second.len()).collect::<Vec<usize>>();
+
+    for (i, c) in first.chars().enumerate() {
+        let mut current = vec![i + 1; second.len() + 1];
+
+        for (j, other) in second.iter().enumerate() {
+            let substitution = previous[j] + usize::from(c != *other);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
 
-    if s.chars().count() != Word::SIZE {
-        return Err("pattern is not five-character long".to_string());
-    } else if !s.chars().all(|c| matches!(c, 'g' | 'y' | 'b')) {
-        return Err("pattern contains unsupported characters".to_string());
+        previous = current;
     }
 
-    let hints: Vec<Hint> = s
+    previous[second.len()]
+}
+
+fn parse_hints(s: &str) -> Result<[Hint; Word::SIZE], WordsError> {
+    let actual = s.chars().count();
+
+    if actual != Word::SIZE {
+        return Err(WordsError::WrongHintCount {
+            expected: Word::SIZE,
+            actual,
+        });
+    }
+
+    let hints = s
         .chars()
-        .map(|c| match c {
-            'g' => Hint::Green,
-            'y' => Hint::Yellow,
-            'b' => Hint::Black,
-            _ => unreachable!(),
-        })
-        .collect();
+        .map(|c| Hint::from_str(c.encode_utf8(&mut [0; 4])))
+        .collect::<Result<Vec<Hint>, _>>()?;
 
     Ok(hints.try_into().unwrap())
 }
 
 fn parse_guess_and_hints(s: &str) -> Result<(Word, [Hint; Word::SIZE]), String> {
     let parts: Vec<&str> = s.split(',').collect();
-
-    if parts.len() != 2 {
+    let [word_part, hints_part] = parts.as_slice() else {
         return Err("input cannot be split in two".to_string());
-    }
+    };
 
-    // SAFETY: `parts` is guaranteed to have a length of two.
-    unsafe {
-        let word = Word::from_str(parts.get_unchecked(0))?;
-        let hints = parse_hints(parts.get_unchecked(1))?;
+    let word = Word::from_str(word_part)?;
+    let hints_part = hints_part.trim();
+    let hints = match parse_hints(hints_part) {
+        Ok(hints) => hints,
+        Err(err) => Pattern::from_emoji(&word, hints_part)
+            .map_err(|_| err)?
+            .hints_array(),
+    };
+    Pattern::try_from_guess_and_hints(&word, &hints)?;
 
-        Ok((word, hints))
-    }
+    Ok((word, hints))
 }
 
-fn load_words<P: AsRef<Path>>(dictionary_path: P) -> Result<Vec<Word>, String> {
-    let file = File::open(dictionary_path).map_err(|err| err.to_string())?;
-    let mut words = BufReader::new(file)
+/// Reads guesses and their hints in the format of [`parse_guess_and_hints`], one per line, from a
+/// file or from stdin if the path is `-`. Blank lines are skipped.
+fn read_guesses_and_hints(path: &Path) -> Result<Vec<(Word, [Hint; Word::SIZE])>, String> {
+    let reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(std::io::stdin().lock())
+    } else {
+        let file = File::open(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Box::new(BufReader::new(file))
+    };
+
+    reader
         .lines()
-        .filter_map(|line| line.ok())
-        .filter_map(|line| Word::from_str(&line).ok())
-        .collect::<Vec<Word>>();
-    words.dedup();
+        .enumerate()
+        .filter_map(|(i, line)| match line {
+            Ok(line) if line.trim().is_empty() => None,
+            Ok(line) => Some(
+                parse_guess_and_hints(line.trim())
+                    .map_err(|err| format!("line {}: {}", i + 1, err)),
+            ),
+            Err(err) => Some(Err(format!("line {}: {}", i + 1, err))),
+        })
+        .collect()
+}
+
+/// Loads the valid words of the dictionary with their frequencies, without duplicates. Each line
+/// holds a word, optionally followed by its frequency (see [`wools::parse_weighted_word`]). The first
+/// occurrence of each word is kept, so the words stay in the order of the dictionary.
+///
+/// Lines which are not valid words are skipped, so that any list of words can be used, but a
+/// valid word followed by an invalid frequency, or a file which cannot be read, is an error.
+fn load_words<P: AsRef<Path>>(dictionary_path: P) -> Result<Vec<(Word, f64)>, WordsError> {
+    let mut words = load_weighted_words_iter(dictionary_path)
+        .filter(|word| match word {
+            Err(WordsError::InvalidLine { error, .. }) => {
+                matches!(**error, WordsError::InvalidFormat(_))
+            }
+            _ => true,
+        })
+        .collect::<Result<Vec<(Word, f64)>, WordsError>>()?;
+    let mut seen = HashSet::new();
+    words.retain(|(word, _)| seen.insert(word.clone()));
     Ok(words)
 }
 
-fn filter(words: Vec<Word>, solution: Word, guesses: Vec<Word>) -> Result<(), String> {
-    for word in wools::filter(&words, &solution, &guesses) {
-        println!("{}", word);
+fn unweighted(words: Vec<(Word, f64)>) -> Vec<Word> {
+    words.into_iter().map(|(word, _)| word).collect()
+}
+
+fn filter(
+    answers: Vec<Word>,
+    allowed: Option<Vec<Word>>,
+    solution: Word,
+    guesses: Vec<Word>,
+    exclude: Vec<Word>,
+    format: Format,
+    limit: usize,
+) -> Result<(), String> {
+    if let Some(allowed) = allowed {
+        let guesses_and_hints = guesses
+            .iter()
+            .map(|guess| {
+                (
+                    guess.clone(),
+                    Pattern::from_solution_and_guess(&solution, guess).hints,
+                )
+            })
+            .collect::<Vec<_>>();
+        wools::solve_with_answers(&guesses_and_hints, &answers, &allowed)?;
     }
 
-    Ok(())
+    print_words(
+        &wools::filter_excluding(&answers, &solution, &guesses, &exclude),
+        format,
+        limit,
+    )
 }
 
-fn matches(words: Vec<Word>, solution: Word, hints: [Hint; Word::SIZE]) -> Result<(), String> {
-    for word in wools::matches(&words, &solution, &hints) {
-        println!("{}", word);
+fn matches(
+    words: Vec<Word>,
+    solution: Word,
+    hints: [Hint; Word::SIZE],
+    format: Format,
+    limit: usize,
+    color: ColorMode,
+) -> Result<(), String> {
+    let words = wools::matches(&words, &solution, &hints);
+
+    match (format, color) {
+        (Format::Json, _) | (Format::Plain, ColorMode::Never) => print_words(&words, format, limit),
+        (Format::Plain, ColorMode::Auto) if !is_colored_terminal() => {
+            print_words(&words, format, limit)
+        }
+        (Format::Plain, _) => {
+            let (shown, more) = limit_words(&words, limit);
+
+            for word in shown {
+                let pattern = Pattern::from_guess_and_hints(word, &hints);
+                println!("{}", pattern.render_ansi());
+            }
+
+            report_more(more);
+            Ok(())
+        }
     }
+}
 
-    Ok(())
+/// Returns whether stdout is a terminal and the user did not opt out of colors with `NO_COLOR`.
+fn is_colored_terminal() -> bool {
+    std::io::stdout().is_terminal()
+        && std::env::var_os(NO_COLOR_VAR).is_none_or(|value| value.is_empty())
 }
 
 fn solve(
+    answers: Vec<(Word, f64)>,
+    allowed: Option<Vec<Word>>,
+    guesses_and_hints: Vec<(Word, [Hint; Word::SIZE])>,
+    format: Format,
+    limit: usize,
+    sorted: bool,
+) -> Result<(), String> {
+    let (answers, frequencies): (Vec<Word>, Vec<f64>) = answers.into_iter().unzip();
+    let mut words = solve_with_answers(&answers, allowed.as_deref(), &guesses_and_hints)?;
+
+    // Unless sorted, the most frequent words come first, the others staying in the order of the
+    // dictionary. Words are loaded without duplicates, so each has a single frequency.
+    if !sorted {
+        let frequencies = answers
+            .iter()
+            .zip(frequencies)
+            .collect::<HashMap<&Word, f64>>();
+        words.sort_by(|first, second| frequencies[second].total_cmp(&frequencies[first]));
+    }
+
+    match format {
+        Format::Plain => print_words(&words, format, limit),
+        Format::Json => {
+            let (shown, more) = limit_words(&words, limit);
+            println!(
+                "{{\"remaining_count\":{},\"words\":{}}}",
+                words.len(),
+                json_words(shown)
+            );
+            report_more(more);
+            Ok(())
+        }
+    }
+}
+
+fn solve_interactively(
     words: Vec<Word>,
     guesses_and_hints: Vec<(Word, [Hint; Word::SIZE])>,
 ) -> Result<(), String> {
-    for word in wools::solve(&words, &guesses_and_hints) {
-        println!("{}", word);
+    let mut candidates = wools::solve(&words, &guesses_and_hints)
+        .into_iter()
+        .cloned()
+        .collect::<Vec<Word>>();
+    let mut lines = std::io::stdin().lock().lines();
+
+    while candidates.len() > 1 {
+        let line = match lines.next() {
+            Some(line) => line.map_err(|err| err.to_string())?,
+            None => return Ok(()),
+        };
+        let guess_and_hints = match parse_guess_and_hints(line.trim()) {
+            Ok(guess_and_hints) => guess_and_hints,
+            Err(err) => {
+                let typed = line.split(',').next().unwrap_or_default().trim();
+                eprintln!("{}", suggest_closest_words(err, typed, &words));
+                continue;
+            }
+        };
+
+        candidates = wools::solve(&candidates, &[guess_and_hints])
+            .into_iter()
+            .cloned()
+            .collect();
+
+        if candidates.len() > 1 {
+            let suggestions = rank_guesses(&candidates, &candidates)
+                .into_iter()
+                .take(SUGGESTION_COUNT)
+                .map(|(guess, _)| guess.to_string())
+                .collect::<Vec<_>>();
+            println!(
+                "{} candidates left, try {}",
+                candidates.len(),
+                suggestions.join(", ")
+            );
+        }
+    }
+
+    match candidates.first() {
+        Some(solution) => println!("The solution is {}", solution),
+        None => return Err("no word matches the hints".to_string()),
     }
 
     Ok(())
 }
 
-fn dict(words: Vec<Word>) -> Result<(), String> {
-    for word in words {
-        println!("{}", word);
+fn count(
+    answers: Vec<Word>,
+    allowed: Option<Vec<Word>>,
+    guesses_and_hints: Vec<(Word, [Hint; Word::SIZE])>,
+) -> Result<(), String> {
+    let words = solve_with_answers(&answers, allowed.as_deref(), &guesses_and_hints)?;
+    println!("{}", words.len());
+
+    Ok(())
+}
+
+fn explain(word: Word, guesses_and_hints: Vec<(Word, [Hint; Word::SIZE])>) {
+    for (i, passed, reason) in wools::explain(&word, &guesses_and_hints) {
+        let (guess, hints) = &guesses_and_hints[i];
+
+        if passed {
+            println!("{},{}: possible", guess, format_hints(hints));
+        } else {
+            println!("{},{}: ruled out, {}", guess, format_hints(hints), reason);
+        }
+    }
+}
+
+fn suggest(
+    answers: Vec<Word>,
+    allowed: Option<Vec<Word>>,
+    guesses_and_hints: Vec<(Word, [Hint; Word::SIZE])>,
+    top: usize,
+    metric: Metric,
+) -> Result<(), String> {
+    let candidates = solve_with_answers(&answers, allowed.as_deref(), &guesses_and_hints)?;
+    let allowed = allowed.as_deref().unwrap_or(&answers);
+    let (ranked, unit) = match metric {
+        Metric::Entropy => (rank_guesses(allowed, &candidates), "bits"),
+        Metric::Minimax => (
+            rank_minimax(allowed, &candidates)
+                .into_iter()
+                .map(|(guess, remaining)| (guess, remaining as f64))
+                .collect(),
+            "words",
+        ),
+        Metric::Average => (
+            best_guess(allowed, &candidates, expected_remaining),
+            "words",
+        ),
+    };
+
+    for (guess, score) in ranked.into_iter().take(top) {
+        println!("{}  {:.2} {}", guess, score, unit);
     }
 
     Ok(())
 }
 
+fn solve_with_answers<'a>(
+    answers: &'a [Word],
+    allowed: Option<&[Word]>,
+    guesses_and_hints: &[(Word, [Hint; Word::SIZE])],
+) -> Result<Vec<&'a Word>, String> {
+    match allowed {
+        Some(allowed) => wools::solve_with_answers(guesses_and_hints, answers, allowed),
+        None => wools::solve_checked(answers, guesses_and_hints),
+    }
+}
+
+fn dict(words: Vec<Word>, stats: bool, format: Format, limit: usize) -> Result<(), String> {
+    if !stats {
+        return print_words(&words, format, limit);
+    }
+
+    let stats = wools::dictionary_stats(&words);
+    println!("words: {}", stats.total);
+    println!("unique words: {}", stats.unique);
+    println!("words with repeated letters: {}", stats.repeated_letters);
+
+    for (letter, count) in (b'a'..=b'z').zip(stats.letter_counts) {
+        println!("{}: {}", letter as char, count);
+    }
+
+    Ok(())
+}
+
+fn print_words<W: AsRef<str>>(words: &[W], format: Format, limit: usize) -> Result<(), String> {
+    let (words, more) = limit_words(words, limit);
+
+    match format {
+        Format::Plain => {
+            for word in words {
+                println!("{}", word.as_ref());
+            }
+        }
+        Format::Json => println!("{}", json_words(words)),
+    }
+
+    report_more(more);
+    Ok(())
+}
+
+/// Writes the words as a JSON array of strings, escaping quotes, backslashes and control
+/// characters, so that the binary does not need the `serde` feature.
+fn json_words<W: AsRef<str>>(words: &[W]) -> String {
+    let words = words
+        .iter()
+        .map(|word| format!("\"{}\"", json_escape(word.as_ref())))
+        .collect::<Vec<String>>();
+    format!("[{}]", words.join(","))
+}
+
+/// Escapes the characters which cannot appear as is in a JSON string.
+fn json_escape(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Keeps the first `limit` words, or every word if the limit is 0, along with how many were left
+/// out.
+fn limit_words<W>(words: &[W], limit: usize) -> (&[W], usize) {
+    if limit == 0 || words.len() <= limit {
+        (words, 0)
+    } else {
+        (&words[..limit], words.len() - limit)
+    }
+}
+
+fn report_more(more: usize) {
+    if more > 0 {
+        eprintln!("... ({} more)", more);
+    }
+}
+
+fn practice(words: Vec<Word>, tries: usize, strict: bool, timed: bool) -> Result<(), String> {
+    let solution = match std::env::var(SOLUTION_VAR) {
+        Ok(solution) => {
+            Word::from_str(&solution).map_err(|err| format!("{}: {}", SOLUTION_VAR, err))?
+        }
+        Err(_) if words.is_empty() => return Err("dictionary is empty".to_string()),
+        Err(_) => words[random_index(words.len())].clone(),
+    };
+    let dictionary = WordList::new(words);
+    let mut game = Game::with_max_guesses(solution, tries);
+    let mut lines = std::io::stdin().lock().lines();
+    let mut timings = Vec::new();
+    let mut start = Instant::now();
+
+    while game.status() == GameStatus::InProgress {
+        let line = match lines.next() {
+            Some(line) => line.map_err(|err| err.to_string())?,
+            None => break,
+        };
+        let guess = match Word::from_str(line.trim()) {
+            Ok(guess) => guess,
+            Err(err) => {
+                eprintln!(
+                    "{}",
+                    suggest_closest_words(err.to_string(), line.trim(), dictionary.words())
+                );
+                continue;
+            }
+        };
+
+        if strict && !dictionary.contains(&guess) {
+            eprintln!("word is not in the dictionary");
+            continue;
+        }
+
+        let hints = format_hints(&game.guess(&guess)?.hints);
+        timings.push(start.elapsed());
+        start = Instant::now();
+
+        match game.remaining_guesses() {
+            1 => println!("{} (1 try left)", hints),
+            remaining => println!("{} ({} tries left)", hints, remaining),
+        }
+    }
+
+    match game.status() {
+        GameStatus::Won if game.patterns().len() == 1 => println!("Solved in 1 guess"),
+        GameStatus::Won => println!("Solved in {} guesses", game.patterns().len()),
+        GameStatus::Lost | GameStatus::InProgress => {
+            println!("Not solved, the solution was {}", game.solution())
+        }
+    }
+
+    if timed {
+        print!("{}", format_timings(&timings));
+    }
+
+    Ok(())
+}
+
+fn format_hints(hints: &[Hint]) -> String {
+    hints.iter().map(Hint::to_string).collect()
+}
+
+fn format_timings(timings: &[Duration]) -> String {
+    let mut summary = String::new();
+
+    for (i, timing) in timings.iter().enumerate() {
+        summary.push_str(&format!("Guess {}: {:.1}s\n", i + 1, timing.as_secs_f64()));
+    }
+
+    let total = timings.iter().sum::<Duration>();
+    summary.push_str(&format!("Total time: {:.1}s\n", total.as_secs_f64()));
+    summary
+}
+
+fn random_index(len: usize) -> usize {
+    RandomState::new().hash_one(len) as usize % len
+}
+
+fn openers(words: Vec<Word>, top: usize, cache: Option<PathBuf>) -> Result<(), String> {
+    let cached = match &cache {
+        Some(path) => read_openers(&words, path)?,
+        None => None,
+    };
+    // A cache holding fewer openers than asked for is only complete if it holds every word.
+    let openers = match cached {
+        Some(openers) if openers.len() >= top.min(words.len()) => {
+            openers.into_iter().take(top).collect()
+        }
+        _ => {
+            let openers = best_openers(&words, top);
+
+            if let Some(path) = cache {
+                write_openers(&words, &openers, &path)?;
+            }
+
+            openers
+        }
+    };
+
+    for (guess, entropy) in openers {
+        println!("{}  {:.2} bits", guess, entropy);
+    }
+
+    Ok(())
+}
+
+/// Reads the openers cached in the file, or returns `None` if the file does not exist or was
+/// written for another dictionary.
+#[cfg(feature = "serde")]
+fn read_openers(words: &[Word], path: &Path) -> Result<Option<Vec<(Word, f64)>>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let file = File::open(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    wools::load_best_openers(words, &mut BufReader::new(file))
+        .map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+#[cfg(not(feature = "serde"))]
+fn read_openers(_: &[Word], _: &Path) -> Result<Option<Vec<(Word, f64)>>, String> {
+    Err(OPENERS_CACHE_UNSUPPORTED.to_string())
+}
+
+#[cfg(feature = "serde")]
+fn write_openers(words: &[Word], openers: &[(Word, f64)], path: &Path) -> Result<(), String> {
+    let mut file = BufWriter::new(File::create(path).map_err(|err| err.to_string())?);
+    wools::save_best_openers(words, openers, &mut file)?;
+    file.flush().map_err(|err| err.to_string())
+}
+
+#[cfg(not(feature = "serde"))]
+fn write_openers(_: &[Word], _: &[(Word, f64)], _: &Path) -> Result<(), String> {
+    Err(OPENERS_CACHE_UNSUPPORTED.to_string())
+}
+
+fn export_tree(words: Vec<Word>, opener: Word, output: PathBuf) -> Result<(), String> {
+    let tree = DecisionTree::build(&words, &opener);
+    let mut file = BufWriter::new(File::create(output).map_err(|err| err.to_string())?);
+
+    tree.write_to(&mut file)
+        .and_then(|_| file.flush())
+        .map_err(|err| err.to_string())
+}
+
 fn open<S: AsRef<OsStr>>(url: S) -> Result<(), String> {
-    let output = std::process::Command::new("xdg-open").arg(url).output();
+    let os = std::env::consts::OS;
+    let (program, args) = launcher(os).ok_or_else(|| format!("cannot open a browser on {}", os))?;
+    let output = std::process::Command::new(program)
+        .args(args)
+        .arg(url)
+        .output();
 
     match output {
         Ok(output) if output.status.success() => Ok(()),
         Ok(output) if output.stderr.is_empty() => Err(output.status.to_string()),
         Ok(output) => Err(format!("{}", String::from_utf8_lossy(&output.stderr))),
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            Err(format!("{} is not installed", program))
+        }
         Err(error) => Err(error.to_string()),
     }
 }
+
+/// Returns the program opening URLs in the default browser on the operating system, as named by
+/// [`std::env::consts::OS`], along with the arguments to pass before the URL.
+fn launcher(os: &str) -> Option<(&'static str, &'static [&'static str])> {
+    match os {
+        "macos" => Some(("open", &[])),
+        // The empty argument is the title of the window, which `start` expects before the URL.
+        "windows" => Some(("cmd", &["/C", "start", ""])),
+        "linux" | "freebsd" | "dragonfly" | "netbsd" | "openbsd" | "solaris" | "illumos" => {
+            Some(("xdg-open", &[]))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        closest_words, edit_distance, json_words, launcher, parse_guess_and_hints, parse_hints,
+        suggest_closest_words,
+    };
+    use wools::WordsError;
+    use wools::{Hint, Word};
+
+    #[test]
+    fn when_edit_distance_then_count_insertions_deletions_and_substitutions() {
+        assert_eq!(0, edit_distance("crane", "crane"));
+        assert_eq!(1, edit_distance("cranes", "crane"));
+        assert_eq!(1, edit_distance("cane", "crane"));
+        assert_eq!(2, edit_distance("carne", "crane"));
+        assert_eq!(5, edit_distance("", "crane"));
+    }
+
+    #[test]
+    fn given_word_of_wrong_length_when_suggest_closest_words_then_list_at_most_three() {
+        let words = ["crane", "crate", "plumb", "crone", "brine", "grate"]
+            .into_iter()
+            .map(Word::new)
+            .collect::<Vec<Word>>();
+
+        assert_eq!(
+            vec![&words[0], &words[1], &words[3]],
+            closest_words("CRANES", &words)
+        );
+        assert_eq!(
+            "word is not 5-character long, did you mean crane, crate or crone?",
+            suggest_closest_words("word is not 5-character long".to_string(), "cranes", &words)
+        );
+        assert_eq!(
+            "word is not 5-character long, did you mean plumb?",
+            suggest_closest_words("word is not 5-character long".to_string(), "plum", &words)
+        );
+        assert_eq!(
+            "word is not 5-character long",
+            suggest_closest_words("word is not 5-character long".to_string(), "xyz", &words)
+        );
+    }
+
+    #[test]
+    fn when_parse_guess_and_hints_then_return_guess_and_hints() {
+        let (word, hints) = parse_guess_and_hints("crane,gybbb").unwrap();
+
+        assert_eq!(Word::new("crane"), word);
+        assert_eq!(
+            [
+                Hint::Green,
+                Hint::Yellow,
+                Hint::Black,
+                Hint::Black,
+                Hint::Black
+            ],
+            hints
+        );
+    }
+
+    #[test]
+    fn given_no_comma_when_parse_guess_and_hints_then_return_error() {
+        assert_eq!(
+            Err("input cannot be split in two".to_string()),
+            parse_guess_and_hints("crane")
+        );
+    }
+
+    #[test]
+    fn given_two_commas_when_parse_guess_and_hints_then_return_error() {
+        assert_eq!(
+            Err("input cannot be split in two".to_string()),
+            parse_guess_and_hints("crane,gybbb,")
+        );
+    }
+
+    #[test]
+    fn given_surrounding_whitespace_when_parse_guess_and_hints_then_ignore_whitespace() {
+        assert_eq!(
+            parse_guess_and_hints("crane,gybbb"),
+            parse_guess_and_hints(" CRANE , gybbb ")
+        );
+        assert!(parse_guess_and_hints("cr ane,gybbb").is_err());
+    }
+
+    #[test]
+    fn given_impossible_hints_when_parse_guess_and_hints_then_return_error() {
+        assert!(parse_guess_and_hints("geese,bbybb").is_err());
+    }
+
+    #[test]
+    fn given_alternate_characters_when_parse_hints_then_return_same_hints() {
+        let hints = parse_hints("gybbb").unwrap();
+
+        assert_eq!(hints, parse_hints("21000").unwrap());
+        assert_eq!(hints, parse_hints("gy.-0").unwrap());
+        assert_eq!(hints, parse_hints("GYBBB").unwrap());
+    }
+
+    #[test]
+    fn given_unsupported_character_when_parse_hints_then_list_accepted_characters() {
+        let err = parse_hints("gybbx").unwrap_err();
+
+        assert_eq!(WordsError::BadHintChar('x'), err);
+        assert_eq!(
+            "unsupported hint 'x', expected g, y, b, 2, 1, 0, . or -",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn given_too_few_hints_when_parse_hints_then_return_wrong_hint_count() {
+        assert_eq!(
+            Err(WordsError::WrongHintCount {
+                expected: 5,
+                actual: 3
+            }),
+            parse_hints("gyb")
+        );
+    }
+
+    #[test]
+    fn given_operating_system_when_launcher_then_return_its_launcher() {
+        assert_eq!(Some(("open", &[][..])), launcher("macos"));
+        assert_eq!(Some(("cmd", &["/C", "start", ""][..])), launcher("windows"));
+        assert_eq!(Some(("xdg-open", &[][..])), launcher("linux"));
+    }
+
+    #[test]
+    fn given_unknown_operating_system_when_launcher_then_return_none() {
+        assert_eq!(None, launcher("ios"));
+    }
+
+    #[test]
+    fn given_words_when_json_words_then_return_array_of_strings() {
+        assert_eq!("[]", json_words::<&str>(&[]));
+        assert_eq!(
+            r#"["fight","été","ёлка"]"#,
+            json_words(&["fight", "été", "ёлка"])
+        );
+    }
+
+    #[test]
+    fn given_characters_needing_escape_when_json_words_then_escape_them() {
+        assert_eq!(
+            r#"["say \"hi\"","back\\slash","new\nline","bell\u0007"]"#,
+            json_words(&["say \"hi\"", "back\\slash", "new\nline", "bell\u{7}"])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn given_any_words_when_json_words_then_serde_json_reads_them_back() {
+        let words = [
+            "fight",
+            "say \"hi\"",
+            "back\\slash",
+            "tab\tnew\nline",
+            "\u{1b}\u{7f}é",
+        ];
+
+        assert_eq!(
+            words.to_vec(),
+            serde_json::from_str::<Vec<String>>(&json_words(&words)).unwrap()
+        );
+    }
+}
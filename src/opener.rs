@@ -0,0 +1,451 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::error::WordsError;
+use crate::strategy::{entropy, rank_guesses};
+use crate::word::Word;
+
+const MAGIC: &[u8; 4] = b"WOPN";
+const VERSION: u8 = 4;
+#[cfg(feature = "serde")]
+const OPENERS_VERSION: u32 = 1;
+
+/// The best openers as written by [`save_best_openers`].
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct SavedOpeners {
+    version: u32,
+    words_hash: String,
+    openers: Vec<(String, f64)>,
+}
+
+/// A precomputed table of the [`entropy`] of every allowed first guess against a dictionary.
+///
+/// Ranking first guesses is the most expensive operation of a game, since every allowed word is
+/// played against every word of the dictionary. The table is computed once, saved with
+/// [`save_opener_table`], and loaded back with [`load_opener_table`] for later games.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{rank_guesses, OpenerTable, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+/// let table = OpenerTable::new(&words, &words);
+///
+/// assert_eq!(rank_guesses(&words, &words), table.rank_guesses(&words, &words));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct OpenerTable {
+    candidates_hash: u64,
+    entropies: HashMap<Word, f64>,
+}
+
+impl OpenerTable {
+    /// Computes the entropy of every allowed guess against the candidates, which are usually the
+    /// whole dictionary.
+    pub fn new<W: Borrow<Word>>(allowed: &[Word], candidates: &[W]) -> Self {
+        OpenerTable {
+            candidates_hash: hash_words(candidates.iter().map(Borrow::borrow)),
+            entropies: rank_guesses(allowed, candidates)
+                .into_iter()
+                .map(|(guess, entropy)| (guess.clone(), entropy))
+                .collect(),
+        }
+    }
+
+    /// Returns the entropy of the guess as a first guess, if it is in the table.
+    pub fn entropy(&self, guess: &Word) -> Option<f64> {
+        self.entropies.get(guess).copied()
+    }
+
+    /// Ranks the allowed guesses like [`rank_guesses`], reading entropies from the table when no
+    /// guess has been made yet.
+    ///
+    /// No guess has been made as long as the candidates are those the table was computed for, in
+    /// the same order, which is told by a hash of the candidates. Allowed guesses missing from the
+    /// table, and every guess against other candidates, are computed.
+    pub fn rank_guesses<'a, W: Borrow<Word>>(
+        &self,
+        allowed: &'a [Word],
+        candidates: &[W],
+    ) -> Vec<(&'a Word, f64)> {
+        if hash_words(candidates.iter().map(Borrow::borrow)) != self.candidates_hash {
+            return rank_guesses(allowed, candidates);
+        }
+
+        let mut ranked = allowed
+            .iter()
+            .map(|guess| {
+                let entropy = self
+                    .entropy(guess)
+                    .unwrap_or_else(|| entropy(guess, candidates));
+                (guess, entropy)
+            })
+            .collect::<Vec<_>>();
+
+        ranked.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+        ranked
+    }
+}
+
+/// Writes the table as the `WOPN` magic bytes, a format version byte (currently `4`), the 64-bit
+/// FNV-1a hash of the candidates as a little-endian `u64`, the number of entries as a
+/// little-endian `u32`, then each entry as its guess and its entropy as a little-endian `f64`, by
+/// ascending guess. A guess is written as a byte holding its length in bytes, its UTF-8 bytes,
+/// then the index of each of its letters as a byte (see [`Word::letters`]), so that guesses of any
+/// alphabet are read back as they were.
+pub fn save_opener_table<W: Write>(table: &OpenerTable, writer: &mut W) -> std::io::Result<()> {
+    let mut entries = table.entropies.iter().collect::<Vec<_>>();
+    entries.sort_by(|(first, _), (second, _)| first.as_str().cmp(second.as_str()));
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION])?;
+    writer.write_all(&table.candidates_hash.to_le_bytes())?;
+    writer.write_all(&(entries.len() as u32).to_le_bytes())?;
+
+    for (guess, entropy) in entries {
+        guess.write_to(writer)?;
+        writer.write_all(&entropy.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Reads a table written by [`save_opener_table`], or returns `None` if it was computed for other
+/// candidates, in which case it must be computed again.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{load_opener_table, save_opener_table, OpenerTable, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+/// let table = OpenerTable::new(&words, &words);
+/// let mut bytes = Vec::new();
+/// save_opener_table(&table, &mut bytes).unwrap();
+///
+/// assert_eq!(Ok(Some(table)), load_opener_table(&words, &mut bytes.as_slice()));
+/// assert_eq!(Ok(None), load_opener_table(&words[1..], &mut bytes.as_slice()));
+/// ```
+pub fn load_opener_table<W: Borrow<Word>, R: Read>(
+    candidates: &[W],
+    reader: &mut R,
+) -> Result<Option<OpenerTable>, String> {
+    let mut header = [0; 17];
+    reader
+        .read_exact(&mut header)
+        .map_err(|err| err.to_string())?;
+
+    if &header[..4] != MAGIC {
+        return Err("not an opener table".to_string());
+    } else if header[4] != VERSION {
+        return Err(format!("unsupported opener table version {}", header[4]));
+    }
+
+    let candidates_hash = u64::from_le_bytes(header[5..13].try_into().unwrap());
+
+    if candidates_hash != hash_words(candidates.iter().map(Borrow::borrow)) {
+        return Ok(None);
+    }
+
+    let entry_count = u32::from_le_bytes(header[13..17].try_into().unwrap());
+    let mut entropies = HashMap::new();
+
+    for _ in 0..entry_count {
+        let guess = Word::read_from(reader)?;
+        let mut entropy = [0; 8];
+        reader
+            .read_exact(&mut entropy)
+            .map_err(|err| err.to_string())?;
+
+        entropies.insert(guess, f64::from_le_bytes(entropy));
+    }
+
+    Ok(Some(OpenerTable {
+        candidates_hash,
+        entropies,
+    }))
+}
+
+/// Finds the `top` best first guesses among the words, by their [`entropy`] against the words,
+/// from the most to the least informative, like [`rank_guesses`] does.
+///
+/// This plays every word against every word, which takes a while for a whole dictionary, so the
+/// result is best saved with `save_best_openers` to be reused.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{best_openers, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert_eq!(Word::new("fight"), best_openers(&words, 1)[0].0);
+/// assert_eq!(3, best_openers(&words, 10).len());
+/// ```
+pub fn best_openers(words: &[Word], top: usize) -> Vec<(Word, f64)> {
+    rank_guesses(words, words)
+        .into_iter()
+        .take(top)
+        .map(|(guess, entropy)| (guess.clone(), entropy))
+        .collect()
+}
+
+/// Writes the best openers of the words as JSON, the hash telling which list of words they were
+/// computed for (see [`load_best_openers`]), such as:
+///
+/// ```json
+/// {"version":1,"words_hash":"baa6e1f91bb8220d","openers":[["might",2.0],["fight",1.5]]}
+/// ```
+///
+/// Requires the `serde` feature.
+#[cfg(feature = "serde")]
+pub fn save_best_openers<W: Write>(
+    words: &[Word],
+    openers: &[(Word, f64)],
+    writer: &mut W,
+) -> Result<(), WordsError> {
+    let saved = SavedOpeners {
+        version: OPENERS_VERSION,
+        words_hash: words_hash(words),
+        openers: openers
+            .iter()
+            .map(|(guess, entropy)| (guess.to_string(), *entropy))
+            .collect(),
+    };
+
+    Ok(serde_json::to_writer(writer, &saved)?)
+}
+
+/// Reads the best openers written by [`save_best_openers`], or returns `None` if they were
+/// computed for another list of words, e.g. once words were added to the dictionary or reordered,
+/// in which case they must be computed again.
+///
+/// Returns an error if the openers cannot be read, or are not in the format written by
+/// [`save_best_openers`]. Requires the `serde` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{best_openers, load_best_openers, save_best_openers, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+/// let openers = best_openers(&words, 2);
+/// let mut bytes = Vec::new();
+/// save_best_openers(&words, &openers, &mut bytes).unwrap();
+///
+/// assert_eq!(Ok(Some(openers)), load_best_openers(&words, &mut bytes.as_slice()));
+/// assert_eq!(Ok(None), load_best_openers(&words[1..], &mut bytes.as_slice()));
+/// ```
+#[cfg(feature = "serde")]
+pub fn load_best_openers<R: Read>(
+    words: &[Word],
+    reader: &mut R,
+) -> Result<Option<Vec<(Word, f64)>>, WordsError> {
+    let saved: SavedOpeners = serde_json::from_reader(reader)?;
+
+    if saved.version != OPENERS_VERSION {
+        return Err(WordsError::UnsupportedVersion(saved.version));
+    } else if saved.words_hash != words_hash(words) {
+        return Ok(None);
+    }
+
+    saved
+        .openers
+        .into_iter()
+        .map(|(guess, entropy)| {
+            let length = guess.chars().count();
+            Ok((Word::with_length(&guess, length)?, entropy))
+        })
+        .collect::<Result<_, WordsError>>()
+        .map(Some)
+}
+
+#[cfg(feature = "serde")]
+fn words_hash(words: &[Word]) -> String {
+    format!("{:016x}", hash_words(words))
+}
+
+/// Hashes the words in order with 64-bit FNV-1a, which unlike the hashers of the standard library
+/// gives the same hash across versions of Rust.
+fn hash_words<'a, I: IntoIterator<Item = &'a Word>>(words: I) -> u64 {
+    words
+        .into_iter()
+        .flat_map(|word| word.as_str().bytes().chain([b'\n']))
+        .fold(0xcbf29ce484222325, |hash: u64, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    use crate::opener::{best_openers, load_best_openers, save_best_openers, words_hash};
+    use crate::opener::{load_opener_table, save_opener_table, OpenerTable};
+    #[cfg(feature = "serde")]
+    use crate::WordsError;
+    use crate::{rank_guesses, Alphabet, Word};
+
+    fn words() -> Vec<Word> {
+        [
+            "fight", "might", "light", "crane", "plumb", "stare", "tears",
+        ]
+        .into_iter()
+        .map(Word::new)
+        .collect()
+    }
+
+    #[test]
+    fn when_save_and_load_opener_table_then_table_is_the_same() {
+        let words = words();
+        let table = OpenerTable::new(&words, &words);
+        let mut bytes = Vec::new();
+
+        save_opener_table(&table, &mut bytes).unwrap();
+
+        assert_eq!(
+            Ok(Some(table)),
+            load_opener_table(&words, &mut bytes.as_slice())
+        );
+    }
+
+    #[test]
+    fn given_words_of_another_alphabet_when_save_and_load_opener_table_then_table_is_the_same() {
+        let russian = Alphabet::new("абвгдежзийклмнопрстуфхцчшщъыьэюя".chars()).unwrap();
+        let words = ["книга", "слово", "почта", "школа"]
+            .map(|word| Word::with_alphabet(word, 5, &russian).unwrap());
+        let table = OpenerTable::new(&words, &words);
+        let mut bytes = Vec::new();
+
+        save_opener_table(&table, &mut bytes).unwrap();
+        let loaded = load_opener_table(&words, &mut bytes.as_slice())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(table, loaded);
+        assert_eq!(
+            rank_guesses(&words, &words),
+            loaded.rank_guesses(&words, &words)
+        );
+    }
+
+    #[test]
+    fn given_bytes_are_truncated_when_load_opener_table_then_return_error() {
+        let words = words();
+        let mut bytes = Vec::new();
+
+        save_opener_table(&OpenerTable::new(&words, &words), &mut bytes).unwrap();
+
+        assert!(load_opener_table(&words, &mut &bytes[..bytes.len() - 1]).is_err());
+        assert!(load_opener_table(&words, &mut b"WOPX".as_slice()).is_err());
+    }
+
+    #[test]
+    fn given_no_guess_made_when_rank_guesses_then_read_entropies_from_table() {
+        let words = words();
+        let mut table = OpenerTable::new(&words, &words);
+        table.entropies.insert(Word::new("plumb"), 100.0);
+
+        assert_eq!(&Word::new("plumb"), table.rank_guesses(&words, &words)[0].0);
+    }
+
+    #[test]
+    fn given_candidates_were_removed_when_rank_guesses_then_compute_entropies() {
+        let words = words();
+        let table = OpenerTable::new(&words, &words);
+
+        assert_eq!(
+            rank_guesses(&words, &words[..3]),
+            table.rank_guesses(&words, &words[..3])
+        );
+    }
+
+    #[test]
+    fn given_other_candidates_of_the_same_size_when_load_opener_table_then_return_none() {
+        let words = words();
+        let mut bytes = Vec::new();
+
+        save_opener_table(&OpenerTable::new(&words, &words[..3]), &mut bytes).unwrap();
+
+        assert_eq!(
+            Ok(None),
+            load_opener_table(&words[3..6], &mut bytes.as_slice())
+        );
+    }
+
+    #[test]
+    fn given_other_candidates_of_the_same_size_when_rank_guesses_then_compute_entropies() {
+        let words = words();
+        let mut table = OpenerTable::new(&words, &words[..3]);
+        table.entropies.insert(Word::new("plumb"), 100.0);
+
+        assert_eq!(
+            rank_guesses(&words, &words[3..6]),
+            table.rank_guesses(&words, &words[3..6])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn given_words_changed_when_load_best_openers_then_return_none() {
+        let words = words();
+        let openers = best_openers(&words, 3);
+        let mut bytes = Vec::new();
+
+        save_best_openers(&words, &openers, &mut bytes).unwrap();
+        let mut reordered = words.clone();
+        reordered.swap(0, 1);
+
+        assert_eq!(
+            Ok(Some(openers)),
+            load_best_openers(&words, &mut bytes.as_slice())
+        );
+        assert_eq!(
+            Ok(None),
+            load_best_openers(&reordered, &mut bytes.as_slice())
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn given_writer_fails_when_save_best_openers_then_return_io_error() {
+        let words = words();
+        let mut full = [0_u8; 8];
+
+        assert!(matches!(
+            save_best_openers(&words, &best_openers(&words, 3), &mut full.as_mut_slice()),
+            Err(WordsError::Io(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn given_invalid_json_when_load_best_openers_then_return_error() {
+        let words = words();
+
+        assert!(matches!(
+            load_best_openers(&words, &mut b"[]".as_slice()),
+            Err(WordsError::InvalidFormat(_))
+        ));
+        assert_eq!(
+            Err(WordsError::UnsupportedVersion(2)),
+            load_best_openers(
+                &words,
+                &mut br#"{"version":2,"words_hash":"","openers":[]}"#.as_slice()
+            )
+        );
+        assert_eq!(
+            Err(WordsError::NonAlphabetic),
+            load_best_openers(
+                &words,
+                &mut format!(
+                    r#"{{"version":1,"words_hash":"{}","openers":[["bob's",1.0]]}}"#,
+                    words_hash(&words)
+                )
+                .as_bytes()
+            )
+        );
+    }
+}
@@ -1,17 +1,39 @@
-use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
+use crate::alphabet::Alphabet;
+use crate::error::WordsError;
 use crate::word::Word;
 
+/// The characters accepted for each hint, `g`, `y` and `b` being the canonical ones. Digits and
+/// punctuation are used by other solvers.
+pub(crate) const HINT_CHARS: [(char, Hint); 8] = [
+    ('g', Hint::Green),
+    ('y', Hint::Yellow),
+    ('b', Hint::Black),
+    ('2', Hint::Green),
+    ('1', Hint::Yellow),
+    ('0', Hint::Black),
+    ('.', Hint::Black),
+    ('-', Hint::Black),
+];
+
 /// A pattern formed by the characters in a word, encoded as an ordered sequence of [`Hint`]s.
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pattern {
     pub guess: Word,
-    pub hints: [Hint; Word::SIZE],
+    pub hints: Vec<Hint>,
 }
 
 impl Pattern {
     /// Creates a pattern from a guess knowing what the solution is.
     ///
+    /// # Panics
+    ///
+    /// Panics if the guess and the solution have different lengths. See
+    /// [`Pattern::try_from_solution_and_guess`] for a non-panicking version.
+    ///
     /// # Examples
     ///
     /// ```
@@ -28,44 +50,75 @@ impl Pattern {
     /// assert_eq!(None, iter.next());
     /// ```
     pub fn from_solution_and_guess(solution: &Word, guess: &Word) -> Self {
-        let mut hints: [Option<Hint>; Word::SIZE] = Default::default();
-        let mut solution_chars = Pattern::count_chars(solution);
+        Pattern::try_from_solution_and_guess(solution, guess).unwrap()
+    }
 
-        for (i, (guess_char, solution_char)) in guess.chars().zip(solution.chars()).enumerate() {
-            if guess_char == solution_char {
-                *solution_chars.get_mut(&guess_char).unwrap() -= 1;
+    /// Creates a pattern from a guess knowing what the solution is, or returns an error if the
+    /// guess and the solution have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Pattern, Word};
+    /// let solution = Word::with_length("rust", 4).unwrap();
+    ///
+    /// assert!(Pattern::try_from_solution_and_guess(&solution, &Word::new("trust")).is_err());
+    /// assert!(Pattern::try_from_solution_and_guess(&solution, &solution).unwrap().is_solved());
+    /// ```
+    pub fn try_from_solution_and_guess(solution: &Word, guess: &Word) -> Result<Self, String> {
+        if solution.length() != guess.length() {
+            return Err(format!(
+                "guess {} and solution {} have different lengths",
+                guess, solution
+            ));
+        }
+
+        let mut hints: Vec<Option<Hint>> = vec![None; guess.length()];
+        let mut solution_letters = [0_u8; Alphabet::MAX_LETTERS];
+
+        for (i, (&guess_letter, &solution_letter)) in
+            guess.letters().iter().zip(solution.letters()).enumerate()
+        {
+            if guess_letter == solution_letter {
                 hints[i] = Some(Hint::Green);
+            } else {
+                solution_letters[solution_letter as usize] += 1;
             }
         }
 
-        for (i, guess_char) in guess.chars().enumerate() {
+        for (i, &guess_letter) in guess.letters().iter().enumerate() {
             if hints[i].is_some() {
                 continue;
             }
 
-            let hint = match solution_chars.get_mut(&guess_char) {
-                Some(0) | None => Hint::Black,
-                Some(count) => {
-                    *count -= 1;
-                    Hint::Yellow
-                }
-            };
-
-            hints[i] = Some(hint);
+            let count = &mut solution_letters[guess_letter as usize];
+            hints[i] = Some(if *count == 0 {
+                Hint::Black
+            } else {
+                *count -= 1;
+                Hint::Yellow
+            });
         }
 
-        Pattern {
+        Ok(Pattern {
             guess: guess.clone(),
-            hints: hints
-                .into_iter()
-                .map(|hint| hint.unwrap())
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap(),
-        }
+            hints: hints.into_iter().map(|hint| hint.unwrap()).collect(),
+        })
     }
 
-    /// Creates a pattern from a guess and the hints it produces.
+    /// Creates a pattern from a guess and the hints it produces, such as the hints observed while
+    /// playing a game, e.g. to build [`Constraints`](crate::Constraints) from them.
+    ///
+    /// When a letter is guessed several times, the hints follow the rules of Wordle: green hints go
+    /// to the copies at the right position, then yellow hints go to the other copies from left to
+    /// right, as long as the solution has copies left. The remaining copies are black. So a black
+    /// copy along with green or yellow copies of the same letter does not mean that the letter is
+    /// absent, but that the solution has exactly as many copies as there are green and yellow
+    /// hints. The hints are not checked, see [`Pattern::try_from_guess_and_hints`] for that.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not exactly one hint per character of the guess.
     ///
     /// # Examples
     ///
@@ -74,13 +127,273 @@ impl Pattern {
     /// let hints = [Hint::Black, Hint::Green, Hint::Yellow, Hint::Black, Hint::Black];
     /// let pattern = Pattern::from_guess_and_hints(&Word::new("attic"), &hints);
     ///
-    /// assert_eq!(hints, pattern.hints);
+    /// assert_eq!(hints, *pattern.hints);
+    /// ```
+    ///
+    /// With a solution having two `t`, the second `t` of the guess is yellow, but the third is
+    /// black since the solution has no `t` left:
+    ///
+    /// ```
+    /// # use wools::{Constraints, Hint, Pattern, Word};
+    /// use Hint::{Black, Green, Yellow};
+    ///
+    /// let pattern = Pattern::from_guess_and_hints(&Word::new("tatty"), &[Green, Black, Yellow, Black, Black]);
+    /// let constraints = Constraints::from_pattern(&pattern);
+    ///
+    /// assert_eq!(pattern, Pattern::from_solution_and_guess(&Word::new("trust"), &Word::new("tatty")));
+    /// assert!(constraints.matches(&Word::new("trust")));
+    /// assert!(!constraints.matches(&Word::new("tutti")));
     /// ```
-    pub fn from_guess_and_hints(guess: &Word, hints: &[Hint; Word::SIZE]) -> Self {
+    pub fn from_guess_and_hints(guess: &Word, hints: &[Hint]) -> Self {
+        assert_eq!(
+            guess.length(),
+            hints.len(),
+            "guess {} does not have one hint per character",
+            guess
+        );
+
         Pattern {
             guess: guess.clone(),
-            hints: *hints,
+            hints: hints.to_vec(),
+        }
+    }
+
+    /// Creates a pattern from a guess and the hints it produces, or returns an error if there is
+    /// not exactly one hint per character of the guess, or if no solution can produce the hints
+    /// (see [`Pattern::is_consistent`]). This catches mistyped hints before they filter out every
+    /// word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Hint, Pattern, Word};
+    /// use Hint::{Black, Green, Yellow};
+    ///
+    /// let guess = Word::new("geese");
+    ///
+    /// assert!(Pattern::try_from_guess_and_hints(&guess, &[Black, Yellow, Black, Black, Green]).is_ok());
+    /// assert!(Pattern::try_from_guess_and_hints(&guess, &[Black, Black, Yellow, Black, Green]).is_err());
+    /// assert!(Pattern::try_from_guess_and_hints(&guess, &[Black, Yellow]).is_err());
+    /// ```
+    pub fn try_from_guess_and_hints(guess: &Word, hints: &[Hint]) -> Result<Self, String> {
+        if guess.length() != hints.len() {
+            return Err(format!(
+                "guess {} does not have one hint per character",
+                guess
+            ));
+        }
+
+        let pattern = Pattern::from_guess_and_hints(guess, hints);
+
+        if pattern.is_consistent() {
+            Ok(pattern)
+        } else {
+            Err(format!("no solution produces these hints for {}", guess))
+        }
+    }
+
+    /// Returns whether some solution produces the hints for the guess.
+    ///
+    /// Hints are inconsistent when a letter is yellow after being black earlier in the guess, since
+    /// yellow hints go to the first copies of a letter, or when there are not enough positions left
+    /// for the yellow letters: a yellow letter can neither be at a green position nor at any of the
+    /// positions where it was guessed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Hint, Pattern, Word};
+    /// use Hint::{Black, Green, Yellow};
+    ///
+    /// let guess = Word::new("geese");
+    ///
+    /// assert!(Pattern::from_guess_and_hints(&guess, &[Black, Yellow, Black, Black, Green]).is_consistent());
+    /// assert!(!Pattern::from_guess_and_hints(&guess, &[Black, Black, Yellow, Black, Green]).is_consistent());
+    /// assert!(!Pattern::from_guess_and_hints(&guess, &[Yellow, Green, Green, Green, Green]).is_consistent());
+    /// ```
+    pub fn is_consistent(&self) -> bool {
+        let free_positions = self.hints.iter().filter(|hint| !hint.is_green()).count();
+        let mut chars = self.guess.chars().collect::<Vec<char>>();
+        let mut yellow_count = 0;
+
+        chars.sort_unstable();
+        chars.dedup();
+
+        for c in chars {
+            let hints = self
+                .guess
+                .chars()
+                .zip(&self.hints)
+                .filter(|(other, hint)| *other == c && !hint.is_green())
+                .map(|(_, hint)| hint)
+                .collect::<Vec<&Hint>>();
+            let yellows = hints.iter().filter(|hint| hint.is_yellow()).count();
+
+            if hints[..yellows].iter().any(|hint| hint.is_black())
+                || yellows > free_positions - hints.len()
+            {
+                return false;
+            }
+
+            yellow_count += yellows;
+        }
+
+        yellow_count <= free_positions
+    }
+
+    /// Creates a pattern from a guess and a row of the emoji grid shared by players, where 🟩 is
+    /// green, 🟨 is yellow, and either ⬛ or ⬜ is black, depending on the theme. Variation
+    /// selectors and whitespace around the emojis are ignored.
+    ///
+    /// Returns an error if the row contains other characters, or if it does not have exactly one
+    /// emoji per character of the guess.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Hint, Pattern, Word};
+    /// let pattern = Pattern::from_emoji(&Word::new("attic"), "⬜🟩🟨⬜⬛").unwrap();
+    ///
+    /// assert_eq!([Hint::Black, Hint::Green, Hint::Yellow, Hint::Black, Hint::Black], *pattern.hints);
+    /// assert!(Pattern::from_emoji(&Word::new("attic"), "🟩🟩").is_err());
+    /// ```
+    pub fn from_emoji(guess: &Word, s: &str) -> Result<Self, String> {
+        let hints = s
+            .trim()
+            .chars()
+            .filter(|&c| c != '\u{fe0f}')
+            .map(|c| match c {
+                '🟩' => Ok(Hint::Green),
+                '🟨' => Ok(Hint::Yellow),
+                '⬛' | '⬜' => Ok(Hint::Black),
+                c => Err(format!("{} is not a hint", c)),
+            })
+            .collect::<Result<Vec<Hint>, String>>()?;
+
+        if hints.len() != guess.length() {
+            return Err(format!("pattern is not {}-emoji long", guess.length()));
+        }
+
+        Ok(Pattern {
+            guess: guess.clone(),
+            hints,
+        })
+    }
+
+    /// Renders the hints as a row of the emoji grid shared by players, where 🟩 is green, 🟨 is
+    /// yellow and ⬛ is black.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Pattern, Word};
+    /// let pattern = Pattern::from_solution_and_guess(&Word::new("stunt"), &Word::new("attic"));
+    ///
+    /// assert_eq!("⬛🟩🟨⬛⬛", pattern.to_emoji());
+    /// ```
+    pub fn to_emoji(&self) -> String {
+        self.hints
+            .iter()
+            .map(|hint| match hint {
+                Hint::Green => '🟩',
+                Hint::Yellow => '🟨',
+                Hint::Black => '⬛',
+            })
+            .collect()
+    }
+
+    /// Renders the guess as tiles colored like in the game, using ANSI escape codes for terminals:
+    /// each uppercase letter has a green, yellow or gray background depending on its hint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Pattern, Word};
+    /// let pattern = Pattern::from_solution_and_guess(&Word::new("stunt"), &Word::new("attic"));
+    /// let tiles = pattern.render_ansi();
+    ///
+    /// assert!(tiles.starts_with("\x1b[1;97;100m A \x1b[0m\x1b[1;97;42m T \x1b[0m"));
+    /// assert_eq!(5, tiles.matches("\x1b[0m").count());
+    /// ```
+    pub fn render_ansi(&self) -> String {
+        self.guess
+            .chars()
+            .zip(&self.hints)
+            .map(|(c, hint)| {
+                let background = match hint {
+                    Hint::Green => 42,
+                    Hint::Yellow => 43,
+                    Hint::Black => 100,
+                };
+                format!(
+                    "\x1b[1;97;{}m {} \x1b[0m",
+                    background,
+                    c.to_ascii_uppercase()
+                )
+            })
+            .collect()
+    }
+
+    /// Encodes the hints as a single integer, each hint being a ternary digit (black is 0, yellow
+    /// is 1, green is 2), and the first hint being the least significant digit. Codes are cheaper
+    /// to hash and compare than hints, e.g. to group many candidates by pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern has more than 10 hints, for which codes would not fit in a `u16`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Pattern, Word};
+    /// let pattern = Pattern::from_solution_and_guess(&Word::new("stunt"), &Word::new("attic"));
+    ///
+    /// // Black, green, yellow, black, black.
+    /// assert_eq!(2 * 3 + 1 * 9, pattern.to_code());
+    /// ```
+    pub fn to_code(&self) -> u16 {
+        code(&self.hints)
+    }
+
+    /// Decodes the hints of a guess from a code produced by [`Pattern::to_code`].
+    ///
+    /// Returns an error if the code has more digits than the guess has characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Hint, Pattern, Word};
+    /// let pattern = Pattern::from_code(&Word::new("attic"), 15).unwrap();
+    ///
+    /// assert_eq!([Hint::Black, Hint::Green, Hint::Yellow, Hint::Black, Hint::Black], *pattern.hints);
+    /// assert!(Pattern::from_code(&Word::new("attic"), 243).is_err());
+    /// ```
+    pub fn from_code(guess: &Word, code: u16) -> Result<Self, String> {
+        let mut rest = code as usize;
+        let hints = (0..guess.length())
+            .map(|_| {
+                let hint = match rest % 3 {
+                    0 => Hint::Black,
+                    1 => Hint::Yellow,
+                    _ => Hint::Green,
+                };
+                rest /= 3;
+                hint
+            })
+            .collect();
+
+        if rest != 0 {
+            return Err(format!(
+                "code {} is too large for {}-character words",
+                code,
+                guess.length()
+            ));
         }
+
+        Ok(Pattern {
+            guess: guess.clone(),
+            hints,
+        })
     }
 
     /// Returns an iterator over the [`Hint`]s of the pattern.
@@ -88,20 +401,99 @@ impl Pattern {
         self.hints.iter()
     }
 
-    fn count_chars(word: &Word) -> HashMap<char, usize> {
-        let mut chars = HashMap::with_capacity(Word::SIZE);
+    /// Returns a copy of the hints as an array, e.g. to pass them on to [`solve`](crate::solve)
+    /// along with the guess.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the guess does not have [`Word::SIZE`] characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{solve, Pattern, Word};
+    /// let words = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+    /// let guess = Word::new("light");
+    /// let pattern = Pattern::from_solution_and_guess(&Word::new("fight"), &guess);
+    ///
+    /// assert_eq!(vec![&words[0], &words[1]], solve(&words, &[(guess, pattern.hints_array())]));
+    /// ```
+    pub fn hints_array(&self) -> [Hint; Word::SIZE] {
+        self.hints.as_slice().try_into().unwrap_or_else(|_| {
+            panic!(
+                "pattern of {} does not have {} hints",
+                self.guess,
+                Word::SIZE
+            )
+        })
+    }
 
-        for char in word.chars() {
-            let count = chars.entry(char).or_insert_with(|| 0_usize);
-            *count += 1;
-        }
+    /// Returns the hints as a reference to an array, like [`Pattern::hints_array`] without copying
+    /// them, e.g. to store the hints of each turn without a vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the guess does not have [`Word::SIZE`] characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Hint, Pattern, Word};
+    /// let pattern = Pattern::from_solution_and_guess(&Word::new("stunt"), &Word::new("attic"));
+    /// let [first, second, ..] = pattern.hints_ref();
+    ///
+    /// assert_eq!((&Hint::Black, &Hint::Green), (first, second));
+    /// ```
+    pub fn hints_ref(&self) -> &[Hint; Word::SIZE] {
+        self.hints.as_slice().try_into().unwrap_or_else(|_| {
+            panic!(
+                "pattern of {} does not have {} hints",
+                self.guess,
+                Word::SIZE
+            )
+        })
+    }
 
-        chars
+    /// Returns whether every hint is green, meaning that the guess is the solution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Pattern, Word};
+    /// let apple = Word::new("apple");
+    ///
+    /// assert!(Pattern::from_solution_and_guess(&apple, &apple).is_solved());
+    /// assert!(!Pattern::from_solution_and_guess(&apple, &Word::new("prime")).is_solved());
+    /// ```
+    pub fn is_solved(&self) -> bool {
+        self.hints.iter().all(Hint::is_green)
     }
 }
 
+/// The number of hints of the longest patterns which can be encoded by [`Pattern::to_code`].
+pub(crate) const MAX_CODE_LENGTH: usize = 10;
+
+/// Encodes the hints as described in [`Pattern::to_code`].
+pub(crate) fn code(hints: &[Hint]) -> u16 {
+    assert!(
+        hints.len() <= MAX_CODE_LENGTH,
+        "patterns of more than {} hints cannot be encoded",
+        MAX_CODE_LENGTH
+    );
+
+    hints.iter().rev().fold(0, |code, hint| {
+        code * 3
+            + match hint {
+                Hint::Black => 0,
+                Hint::Yellow => 1,
+                Hint::Green => 2,
+            }
+    })
+}
+
 /// A hint used to constrain the set of characters that may appear in the solution.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Hint {
     /// A green hint means that the same character is at the same position in the solution.
     Green,
@@ -112,10 +504,83 @@ pub enum Hint {
     Black,
 }
 
+impl Hint {
+    /// Returns whether the hint is [`Hint::Green`].
+    pub fn is_green(&self) -> bool {
+        matches!(self, Hint::Green)
+    }
+
+    /// Returns whether the hint is [`Hint::Yellow`].
+    pub fn is_yellow(&self) -> bool {
+        matches!(self, Hint::Yellow)
+    }
+
+    /// Returns whether the hint is [`Hint::Black`].
+    pub fn is_black(&self) -> bool {
+        matches!(self, Hint::Black)
+    }
+}
+
+impl Display for Hint {
+    /// Formats the hint as its canonical character, `g`, `y` or `b`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Hint;
+    /// assert_eq!("g", Hint::Green.to_string());
+    /// assert_eq!("b", Hint::Black.to_string());
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Hint::Green => 'g',
+            Hint::Yellow => 'y',
+            Hint::Black => 'b',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+impl FromStr for Hint {
+    type Err = WordsError;
+
+    /// Parses a hint from a single character, either `g`, `y` or `b`, `2`, `1` or `0` as used by
+    /// other solvers, or `.` and `-` for black hints. Uppercase letters are accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use wools::{Hint, WordsError};
+    /// assert_eq!(Ok(Hint::Green), Hint::from_str("g"));
+    /// assert_eq!(Ok(Hint::Yellow), Hint::from_str("1"));
+    /// assert_eq!(Ok(Hint::Black), Hint::from_str(&Hint::Black.to_string()));
+    /// assert_eq!(Err(WordsError::BadHintChar('x')), Hint::from_str("x"));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => HINT_CHARS
+                .iter()
+                .find(|(other, _)| *other == c.to_ascii_lowercase())
+                .map(|(_, hint)| *hint)
+                .ok_or(WordsError::BadHintChar(c)),
+            _ => Err(WordsError::WrongHintCount {
+                expected: 1,
+                actual: s.chars().count(),
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use crate::pattern::Hint;
-    use crate::{Pattern, Word};
+    use crate::{load_default_words, Pattern, Word};
 
     #[test]
     fn given_no_guess_char_matches_when_from_solution_and_guess_then_every_hint_is_black() {
@@ -227,6 +692,76 @@ mod tests {
         assert_eq!(None, iter.next());
     }
 
+    #[test]
+    fn when_is_green_or_is_yellow_or_is_black_then_only_matching_predicate_is_true() {
+        assert!(Hint::Green.is_green() && !Hint::Green.is_yellow() && !Hint::Green.is_black());
+        assert!(!Hint::Yellow.is_green() && Hint::Yellow.is_yellow() && !Hint::Yellow.is_black());
+        assert!(!Hint::Black.is_green() && !Hint::Black.is_yellow() && Hint::Black.is_black());
+    }
+
+    #[test]
+    fn given_every_hint_is_green_when_is_solved_then_return_true() {
+        let pattern = Word::new("apple").self_pattern();
+
+        assert!(pattern.is_solved());
+    }
+
+    #[test]
+    fn given_a_hint_is_not_green_when_is_solved_then_return_false() {
+        let hints = [
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+            Hint::Yellow,
+        ];
+        let pattern = Pattern::from_guess_and_hints(&Word::new("apple"), &hints);
+
+        assert!(!pattern.is_solved());
+    }
+
+    #[test]
+    fn given_words_have_another_length_when_from_solution_and_guess_then_hints_match_length() {
+        let solution = Word::with_length("stunts", 6).unwrap();
+        let guess = Word::with_length("attics", 6).unwrap();
+        let pattern = Pattern::from_solution_and_guess(&solution, &guess);
+
+        assert_eq!(
+            vec![
+                Hint::Black,
+                Hint::Green,
+                Hint::Yellow,
+                Hint::Black,
+                Hint::Black,
+                Hint::Green
+            ],
+            pattern.hints
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "different lengths")]
+    fn given_lengths_differ_when_from_solution_and_guess_then_panic() {
+        Pattern::from_solution_and_guess(
+            &Word::new("apple"),
+            &Word::with_length("pear", 4).unwrap(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not have 5 hints")]
+    fn given_longer_guess_when_hints_array_then_panic() {
+        let guess = Word::with_length("plumbs", 6).unwrap();
+
+        guess.self_pattern().hints_array();
+    }
+
+    #[test]
+    #[should_panic(expected = "one hint per character")]
+    fn given_hint_count_differs_from_length_when_from_guess_and_hints_then_panic() {
+        Pattern::from_guess_and_hints(&Word::new("apple"), &[Hint::Green; 4]);
+    }
+
     #[test]
     fn when_from_guess_and_hints_then_hints_are_the_same() {
         let guess = Word::new("apple");
@@ -239,6 +774,271 @@ mod tests {
         ];
         let pattern = Pattern::from_guess_and_hints(&guess, &hints);
 
-        assert_eq!(hints, pattern.hints);
+        assert_eq!(hints, *pattern.hints);
+    }
+
+    #[test]
+    fn when_to_emoji_and_from_emoji_then_hints_are_the_same() {
+        let pattern = Pattern::from_solution_and_guess(&Word::new("leech"), &Word::new("tepee"));
+        let parsed = Pattern::from_emoji(&pattern.guess, &pattern.to_emoji()).unwrap();
+
+        assert_eq!("⬛🟩⬛🟨⬛", pattern.to_emoji());
+        assert_eq!(pattern.hints, parsed.hints);
+    }
+
+    #[test]
+    fn given_light_mode_row_with_variation_selectors_when_from_emoji_then_white_squares_are_black()
+    {
+        let pattern = Pattern::from_emoji(&Word::new("apple"), " ⬜️🟩⬜️🟨⬜️\n").unwrap();
+
+        assert_eq!(
+            [
+                Hint::Black,
+                Hint::Green,
+                Hint::Black,
+                Hint::Yellow,
+                Hint::Black
+            ],
+            *pattern.hints
+        );
+    }
+
+    #[test]
+    fn given_row_is_invalid_when_from_emoji_then_return_error() {
+        assert!(Pattern::from_emoji(&Word::new("apple"), "🟩🟩🟩🟩").is_err());
+        assert!(Pattern::from_emoji(&Word::new("apple"), "🟩🟩🟩🟩🟩🟩").is_err());
+        assert!(Pattern::from_emoji(&Word::new("apple"), "🟩🟩🟥🟩🟩").is_err());
+    }
+
+    #[test]
+    fn when_from_code_and_to_code_then_every_code_is_the_same() {
+        let guess = Word::new("apple");
+
+        for code in 0..243 {
+            assert_eq!(code, Pattern::from_code(&guess, code).unwrap().to_code());
+        }
+    }
+
+    #[test]
+    fn when_to_code_and_from_code_then_every_reachable_pattern_is_the_same() {
+        let words = load_default_words();
+        let guess = Word::new("geese");
+
+        for solution in words.iter().step_by(7) {
+            let pattern = Pattern::from_solution_and_guess(solution, &guess);
+            let decoded = Pattern::from_code(&guess, pattern.to_code()).unwrap();
+
+            assert_eq!(pattern.hints, decoded.hints);
+        }
+    }
+
+    #[test]
+    fn given_every_hint_is_green_when_to_code_then_code_is_largest() {
+        assert_eq!(242, Word::new("apple").self_pattern().to_code());
+        assert_eq!(
+            0,
+            Pattern::from_guess_and_hints(&Word::new("apple"), &[Hint::Black; 5]).to_code()
+        );
+    }
+
+    #[test]
+    fn when_patterns_are_map_keys_then_count_words_producing_each_pattern() {
+        let guess = Word::new("might");
+        let mut counts: HashMap<Pattern, usize> = HashMap::new();
+
+        for solution in ["fight", "light", "sight", "crane", "plumb", "might"] {
+            *counts
+                .entry(Pattern::from_solution_and_guess(
+                    &Word::new(solution),
+                    &guess,
+                ))
+                .or_default() += 1;
+        }
+
+        assert_eq!(4, counts.len());
+        assert_eq!(
+            3,
+            counts[&Pattern::from_solution_and_guess(&Word::new("fight"), &guess)]
+        );
+        assert_eq!(1, counts[&guess.self_pattern()]);
+    }
+
+    #[test]
+    fn given_same_hints_for_different_guesses_when_eq_then_patterns_differ() {
+        let hints = [Hint::Black; 5];
+
+        assert_ne!(
+            Pattern::from_guess_and_hints(&Word::new("crane"), &hints),
+            Pattern::from_guess_and_hints(&Word::new("plumb"), &hints)
+        );
+    }
+
+    #[test]
+    fn when_is_consistent_then_every_pattern_produced_by_a_solution_is_consistent() {
+        let words = load_default_words();
+
+        for guess in words.iter().step_by(211) {
+            for solution in words.iter().step_by(13) {
+                assert!(Pattern::from_solution_and_guess(solution, guess).is_consistent());
+            }
+        }
+    }
+
+    #[test]
+    fn given_yellow_after_black_for_same_letter_when_is_consistent_then_return_false() {
+        let hints = [
+            Hint::Black,
+            Hint::Black,
+            Hint::Yellow,
+            Hint::Black,
+            Hint::Black,
+        ];
+
+        assert!(!Pattern::from_guess_and_hints(&Word::new("geese"), &hints).is_consistent());
+    }
+
+    #[test]
+    fn given_too_many_yellows_when_is_consistent_then_return_false() {
+        let one_free_position = [
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+            Hint::Yellow,
+        ];
+        let no_position_left = [
+            Hint::Yellow,
+            Hint::Yellow,
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+        ];
+
+        assert!(
+            !Pattern::from_guess_and_hints(&Word::new("crane"), &one_free_position).is_consistent()
+        );
+        assert!(
+            !Pattern::from_guess_and_hints(&Word::new("llama"), &no_position_left).is_consistent()
+        );
+    }
+
+    #[test]
+    fn given_swapped_letters_when_is_consistent_then_return_true() {
+        let hints = [
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+            Hint::Yellow,
+            Hint::Yellow,
+        ];
+
+        assert!(Pattern::from_guess_and_hints(&Word::new("stare"), &hints).is_consistent());
+    }
+
+    #[test]
+    fn given_inconsistent_hints_when_try_from_guess_and_hints_then_return_error() {
+        let hints = [
+            Hint::Yellow,
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+            Hint::Green,
+        ];
+
+        assert!(Pattern::try_from_guess_and_hints(&Word::new("crane"), &hints).is_err());
+        assert!(Pattern::try_from_guess_and_hints(&Word::new("crane"), &hints[1..]).is_err());
+    }
+
+    /// Computes hints the naive way, by removing the matched characters from the solution.
+    fn reference_hints(solution: &Word, guess: &Word) -> Vec<Hint> {
+        let guess = guess.chars().collect::<Vec<char>>();
+        let mut solution = solution.chars().map(Some).collect::<Vec<Option<char>>>();
+        let mut hints = vec![Hint::Black; guess.len()];
+
+        for (i, c) in guess.iter().enumerate() {
+            if solution[i] == Some(*c) {
+                hints[i] = Hint::Green;
+                solution[i] = None;
+            }
+        }
+
+        for (i, c) in guess.iter().enumerate() {
+            if hints[i] != Hint::Green {
+                if let Some(j) = solution.iter().position(|other| *other == Some(*c)) {
+                    hints[i] = Hint::Yellow;
+                    solution[j] = None;
+                }
+            }
+        }
+
+        hints
+    }
+
+    #[test]
+    fn when_from_solution_and_guess_then_hints_are_the_same_as_counting_characters() {
+        let words = load_default_words();
+        let guesses = [
+            "geese", "eerie", "llama", "attic", "stunt", "soare", "mamma",
+        ];
+
+        for guess in guesses
+            .map(Word::new)
+            .iter()
+            .chain(words.iter().step_by(997))
+        {
+            for solution in words.iter().step_by(17) {
+                assert_eq!(
+                    reference_hints(solution, guess),
+                    Pattern::from_solution_and_guess(solution, guess).hints,
+                    "{} against {}",
+                    guess,
+                    solution
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn when_to_string_and_from_str_then_hint_is_the_same() {
+        for hint in [Hint::Green, Hint::Yellow, Hint::Black] {
+            assert_eq!(Ok(hint), hint.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn given_several_characters_when_from_str_then_return_error() {
+        assert!("gy".parse::<Hint>().is_err());
+        assert!("".parse::<Hint>().is_err());
+    }
+
+    #[test]
+    fn when_clone_then_pattern_and_hints_are_equal() {
+        let pattern = Pattern::from_solution_and_guess(&Word::new("beret"), &Word::new("geese"));
+        let turns = [pattern.clone(), pattern.clone()];
+
+        assert_eq!(pattern, turns[1]);
+        assert_eq!(pattern.hints_ref(), turns[0].hints_ref());
+        assert_eq!(&pattern.hints_array(), turns[1].hints_ref());
+    }
+
+    #[test]
+    #[should_panic]
+    fn given_longer_guess_when_hints_ref_then_panic() {
+        let guess = Word::with_length("plumbs", 6).unwrap();
+
+        guess.self_pattern().hints_ref();
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn when_serialize_and_deserialize_then_pattern_is_the_same() {
+        let pattern = Pattern::from_solution_and_guess(&Word::new("beret"), &Word::new("geese"));
+        let json = serde_json::to_string(&pattern).unwrap();
+
+        assert_eq!(
+            r#"{"guess":"geese","hints":["Black","Green","Yellow","Black","Black"]}"#,
+            json
+        );
+        assert_eq!(pattern, serde_json::from_str(&json).unwrap());
     }
 }
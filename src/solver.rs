@@ -0,0 +1,293 @@
+use std::collections::BTreeSet;
+
+use crate::constraint::Constraints;
+use crate::pattern::{Hint, Pattern};
+use crate::strategy::{entropy, rank_guesses};
+use crate::word::Word;
+
+/// Narrows down the possible solutions as guesses and their hints are observed during a game.
+///
+/// Each observation only filters the candidates left by the previous ones, so the history of the
+/// game does not have to be passed again, unlike with [`solve`](crate::solve).
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{Hint, Solver, Word};
+/// let words = [Word::new("apple"), Word::new("prime"), Word::new("torch")];
+/// let mut solver = Solver::new(&words);
+///
+/// solver.observe(&Word::new("coupe"), &[Hint::Black, Hint::Black, Hint::Black, Hint::Yellow, Hint::Green]);
+///
+/// assert_eq!(&[&Word::new("apple"), &Word::new("prime")], solver.candidates());
+/// assert!(solver.absent_letters().contains(&'c'));
+///
+/// let allowed = [Word::new("plumb"), Word::new("prime")];
+/// assert_eq!(&Word::new("plumb"), solver.suggest(&allowed)[0].0);
+/// ```
+#[derive(Debug)]
+pub struct Solver<'a> {
+    candidates: Vec<&'a Word>,
+    absent_letters: BTreeSet<char>,
+}
+
+impl<'a> Solver<'a> {
+    /// Creates a solver for which every word may be the solution.
+    pub fn new(words: &'a [Word]) -> Self {
+        Solver {
+            candidates: words.iter().collect(),
+            absent_letters: BTreeSet::new(),
+        }
+    }
+
+    /// Observes the hints produced by a guess, removing the candidates they rule out.
+    pub fn observe(&mut self, guess: &Word, hints: &[Hint]) {
+        let pattern = Pattern::from_guess_and_hints(guess, hints);
+        let constraints = Constraints::from_pattern(&pattern);
+
+        self.candidates.retain(|word| constraints.matches(word));
+
+        for (char, hint) in guess.chars().zip(hints) {
+            let only_black = guess
+                .chars()
+                .zip(hints)
+                .filter(|(c, _)| *c == char)
+                .all(|(_, hint)| hint.is_black());
+
+            if hint.is_black() && only_black {
+                self.absent_letters.insert(char);
+            }
+        }
+    }
+
+    /// Returns the words which may still be the solution.
+    pub fn candidates(&self) -> &[&'a Word] {
+        &self.candidates
+    }
+
+    /// Ranks the allowed guesses by the information they give on average about the candidates,
+    /// like [`rank_guesses`], so that the first one is the best next guess.
+    pub fn suggest<'b>(&self, allowed: &'b [Word]) -> Vec<(&'b Word, f64)> {
+        rank_guesses(allowed, &self.candidates)
+    }
+
+    /// Returns the letters known to be absent from the solution across every observed guess.
+    ///
+    /// A letter which is black for some of its occurrences in a guess, but green or yellow for the
+    /// others, is present in the solution: it is absent only beyond the revealed count, so it is
+    /// not part of the returned letters.
+    pub fn absent_letters(&self) -> BTreeSet<char> {
+        self.absent_letters.clone()
+    }
+}
+
+/// Narrows down the possible solutions of several boards played at once, as in Dordle or Quordle,
+/// where every guess is played on each board but produces different hints on each.
+///
+/// Each board has its own [`Solver`]. A board is solved once a guess gets an all-green pattern on
+/// it, after which it is left out of the suggestions.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{MultiBoard, Pattern, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("crane"), Word::new("plumb")];
+/// let mut boards = MultiBoard::new(&words, 2);
+/// let guess = Word::new("crane");
+///
+/// for (board, solution) in ["fight", "crane"].into_iter().enumerate() {
+///     let pattern = Pattern::from_solution_and_guess(&Word::new(solution), &guess);
+///     boards.observe(board, &guess, &pattern.hints);
+/// }
+///
+/// assert_eq!(&[&words[0], &words[1], &words[3]], boards.candidates(0));
+/// assert!(boards.is_solved(1));
+/// assert_eq!(&Word::new("fight"), boards.suggest(&words)[0].0);
+/// ```
+#[derive(Debug)]
+pub struct MultiBoard<'a> {
+    boards: Vec<Solver<'a>>,
+    solved: Vec<bool>,
+}
+
+impl<'a> MultiBoard<'a> {
+    /// Creates the given number of boards, for which every word may be the solution.
+    pub fn new(words: &'a [Word], boards: usize) -> Self {
+        MultiBoard {
+            boards: (0..boards).map(|_| Solver::new(words)).collect(),
+            solved: vec![false; boards],
+        }
+    }
+
+    /// Returns the number of boards.
+    pub fn len(&self) -> usize {
+        self.boards.len()
+    }
+
+    /// Returns whether there are no boards.
+    pub fn is_empty(&self) -> bool {
+        self.boards.is_empty()
+    }
+
+    /// Observes the hints a guess produced on one of the boards, removing the candidates they
+    /// rule out on that board only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no board at the index.
+    pub fn observe(&mut self, board: usize, guess: &Word, hints: &[Hint]) {
+        self.boards[board].observe(guess, hints);
+        self.solved[board] |= hints.iter().all(Hint::is_green);
+    }
+
+    /// Returns the words which may still be the solution of one of the boards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no board at the index.
+    pub fn candidates(&self, board: usize) -> &[&'a Word] {
+        self.boards[board].candidates()
+    }
+
+    /// Returns whether one of the boards is solved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no board at the index.
+    pub fn is_solved(&self, board: usize) -> bool {
+        self.solved[board]
+    }
+
+    /// Ranks the allowed guesses by the information they give on the unsolved boards, so that the
+    /// first one is the best next guess.
+    ///
+    /// The score of a guess is the sum of its [`entropy`] on the candidates of every unsolved
+    /// board, in bits. Since the boards are independent, this is the information the guess gives
+    /// about all of them together. Guesses with equal scores stay in the order of `allowed`.
+    pub fn suggest<'b>(&self, allowed: &'b [Word]) -> Vec<(&'b Word, f64)> {
+        let unsolved = self
+            .boards
+            .iter()
+            .zip(&self.solved)
+            .filter(|(_, solved)| !**solved)
+            .map(|(board, _)| board.candidates())
+            .collect::<Vec<&[&Word]>>();
+        let mut ranked = allowed
+            .iter()
+            .map(|guess| {
+                let score = unsolved
+                    .iter()
+                    .map(|candidates| entropy(guess, candidates))
+                    .sum::<f64>();
+                (guess, score)
+            })
+            .collect::<Vec<_>>();
+
+        ranked.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::solver::{MultiBoard, Solver};
+    use crate::strategy::{entropy, rank_guesses};
+    use crate::{Pattern, Word};
+
+    fn words() -> Vec<Word> {
+        ["apple", "prime", "plume", "torch", "beret", "geese"]
+            .into_iter()
+            .map(Word::new)
+            .collect()
+    }
+
+    #[test]
+    fn when_observe_then_candidates_are_filtered() {
+        let words = words();
+        let mut solver = Solver::new(&words);
+        let pattern = Pattern::from_solution_and_guess(&Word::new("torch"), &Word::new("apple"));
+
+        solver.observe(&pattern.guess, &pattern.hints);
+
+        assert_eq!(&[&Word::new("torch")], solver.candidates());
+    }
+
+    #[test]
+    fn given_multiple_guesses_when_absent_letters_then_return_union_of_absent_letters() {
+        let words = words();
+        let mut solver = Solver::new(&words);
+
+        for guess in ["crane", "moist"] {
+            let pattern = Pattern::from_solution_and_guess(&Word::new("plume"), &Word::new(guess));
+            solver.observe(&pattern.guess, &pattern.hints);
+        }
+
+        assert_eq!(
+            BTreeSet::from(['a', 'c', 'i', 'n', 'o', 'r', 's', 't']),
+            solver.absent_letters()
+        );
+    }
+
+    #[test]
+    fn given_letter_is_absent_beyond_its_count_when_absent_letters_then_letter_is_not_absent() {
+        let words = words();
+        let mut solver = Solver::new(&words);
+        let pattern = Pattern::from_solution_and_guess(&Word::new("beret"), &Word::new("geese"));
+
+        solver.observe(&pattern.guess, &pattern.hints);
+
+        assert_eq!(BTreeSet::from(['g', 's']), solver.absent_letters());
+        assert_eq!(&[&Word::new("beret")], solver.candidates());
+    }
+
+    #[test]
+    fn given_observed_guesses_when_suggest_then_rank_guesses_against_remaining_candidates() {
+        let words = words();
+        let mut solver = Solver::new(&words);
+        let pattern = Pattern::from_solution_and_guess(&Word::new("prime"), &Word::new("torch"));
+
+        solver.observe(&pattern.guess, &pattern.hints);
+
+        assert_eq!(
+            rank_guesses(&words, solver.candidates()),
+            solver.suggest(&words)
+        );
+        assert!(solver.suggest(&[]).is_empty());
+    }
+
+    #[test]
+    fn when_observe_on_a_board_then_only_its_candidates_are_filtered() {
+        let words = words();
+        let mut boards = MultiBoard::new(&words, 2);
+        let pattern = Pattern::from_solution_and_guess(&Word::new("torch"), &Word::new("apple"));
+
+        boards.observe(0, &pattern.guess, &pattern.hints);
+
+        assert_eq!(&[&Word::new("torch")], boards.candidates(0));
+        assert_eq!(words.len(), boards.candidates(1).len());
+        assert!(!boards.is_solved(0));
+    }
+
+    #[test]
+    fn given_boards_when_suggest_then_sum_entropy_of_unsolved_boards() {
+        let words = words();
+        let mut boards = MultiBoard::new(&words, 3);
+        let guess = Word::new("prime");
+
+        for (board, solution) in ["apple", "prime", "torch"].into_iter().enumerate() {
+            let pattern = Pattern::from_solution_and_guess(&Word::new(solution), &guess);
+            boards.observe(board, &guess, &pattern.hints);
+        }
+
+        let suggestions = boards.suggest(&words);
+
+        assert!(boards.is_solved(1));
+        for (guess, score) in suggestions {
+            let expected =
+                entropy(guess, boards.candidates(0)) + entropy(guess, boards.candidates(2));
+            assert_eq!(expected, score);
+        }
+    }
+}
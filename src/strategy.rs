@@ -0,0 +1,1022 @@
+use std::borrow::Borrow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::alphabet::Alphabet;
+use crate::analysis::candidate_heatmap;
+use crate::constraint::Constraints;
+use crate::pattern::{Hint, Pattern};
+use crate::word::Word;
+
+/// The number of candidates at or below which [`best_guess_adaptive`] guesses a candidate.
+pub const DEFAULT_ENDGAME_THRESHOLD: usize = 2;
+
+/// The penalty, in bits, applied by [`rank_guesses_avoiding_absent`] to each letter known absent.
+/// It is small enough to only reorder guesses which are about as informative.
+pub const DEFAULT_ABSENT_LETTER_WEIGHT: f64 = 0.01;
+
+/// The bonus, in bits, applied by [`rank_guesses_favoring_candidates`] to a guess which wins for
+/// sure. It is small enough to only reorder guesses which are about as informative.
+pub const DEFAULT_WIN_PROBABILITY_WEIGHT: f64 = 0.1;
+
+/// Groups the words by the pattern the guess produces against each of them, i.e. by the hints
+/// which would be shown if the word were the solution. This is the building block of the scores of
+/// guesses, such as [`entropy`], which only need the sizes of the groups (see
+/// [`pattern_distribution`]).
+///
+/// There are at most `3.pow(guess.length())` patterns, and no pattern has an empty group. When the
+/// guess is one of the words, the all-green pattern only holds the guess. Words are kept in the
+/// order of `words` within each group.
+///
+/// # Panics
+///
+/// Panics if a word is not as long as the guess.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{partition, Hint, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+/// let partition = partition(&Word::new("light"), &words);
+///
+/// assert_eq!(vec![&words[0], &words[1]], partition[&vec![Hint::Black, Hint::Green, Hint::Green, Hint::Green, Hint::Green]]);
+/// assert_eq!(vec![&words[2]], partition[&vec![Hint::Black; wools::SIZE]]);
+/// ```
+pub fn partition<'a>(guess: &Word, words: &'a [Word]) -> HashMap<Vec<Hint>, Vec<&'a Word>> {
+    let mut partition: HashMap<Vec<Hint>, Vec<&Word>> = HashMap::new();
+
+    for word in words {
+        let hints = Pattern::from_solution_and_guess(word, guess).hints;
+        partition.entry(hints).or_default().push(word);
+    }
+
+    partition
+}
+
+/// Counts how many candidates produce each pattern when the guess is played against them.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{pattern_distribution, Hint, Word};
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+/// let distribution = pattern_distribution(&Word::new("light"), &candidates);
+///
+/// assert_eq!(2, distribution[&vec![Hint::Black, Hint::Green, Hint::Green, Hint::Green, Hint::Green]]);
+/// assert_eq!(1, distribution[&vec![Hint::Black; wools::SIZE]]);
+/// ```
+pub fn pattern_distribution<W: Borrow<Word>>(
+    guess: &Word,
+    candidates: &[W],
+) -> HashMap<Vec<Hint>, usize> {
+    let mut distribution = HashMap::new();
+
+    for candidate in candidates {
+        let hints = Pattern::from_solution_and_guess(candidate.borrow(), guess).hints;
+        *distribution.entry(hints).or_insert(0) += 1;
+    }
+
+    distribution
+}
+
+/// Computes the Shannon entropy, in bits, of the patterns produced by the guess against the
+/// candidates, i.e. how much information the guess is expected to give.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{entropy, Word};
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert_eq!(0.0, entropy(&Word::new("plows"), &candidates));
+/// assert!((entropy(&Word::new("frame"), &candidates) - 3_f64.log2()).abs() < 1e-9);
+/// ```
+pub fn entropy<W: Borrow<Word>>(guess: &Word, candidates: &[W]) -> f64 {
+    distribution_entropy(pattern_distribution(guess, candidates).into_values())
+}
+
+fn distribution_entropy<I: Iterator<Item = usize>>(counts: I) -> f64 {
+    // Counts are sorted so that the sum, hence the entropy, does not depend on the map order.
+    let mut counts = counts.collect::<Vec<usize>>();
+    counts.sort_unstable();
+    let total = counts.iter().sum::<usize>() as f64;
+
+    counts
+        .into_iter()
+        .map(|count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Computes the expected number of candidates remaining after the guess is played, i.e. the
+/// expected size of the group of candidates producing the same pattern as the solution. Returns 0
+/// when there are no candidates.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{expected_remaining, Word};
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert!((expected_remaining(&Word::new("light"), &candidates) - 5.0 / 3.0).abs() < 1e-9);
+/// assert!((expected_remaining(&Word::new("frame"), &candidates) - 1.0).abs() < 1e-9);
+/// ```
+pub fn expected_remaining<W: Borrow<Word>>(guess: &Word, candidates: &[W]) -> f64 {
+    if candidates.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = pattern_distribution(guess, candidates)
+        .into_values()
+        .collect::<Vec<_>>();
+    counts.sort_unstable();
+
+    let squares = counts.into_iter().map(|count| count * count).sum::<usize>();
+    squares as f64 / candidates.len() as f64
+}
+
+/// Computes the number of candidates remaining after the guess is played in the worst case, i.e.
+/// the size of the largest group of candidates producing the same pattern. Returns 0 when there
+/// are no candidates.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{worst_case_remaining, Word};
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert_eq!(2.0, worst_case_remaining(&Word::new("light"), &candidates));
+/// assert_eq!(1.0, worst_case_remaining(&Word::new("frame"), &candidates));
+/// ```
+pub fn worst_case_remaining<W: Borrow<Word>>(guess: &Word, candidates: &[W]) -> f64 {
+    pattern_distribution(guess, candidates)
+        .into_values()
+        .max()
+        .unwrap_or(0) as f64
+}
+
+/// Ranks the allowed guesses by their [`entropy`] against the candidates, from the most to the
+/// least informative. Guesses with equal entropy are kept in the order of `allowed`, so that the
+/// ranking is stable.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{rank_guesses, Word};
+/// let allowed = [Word::new("plows"), Word::new("light"), Word::new("frame"), Word::new("fight")];
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+/// let ranked = rank_guesses(&allowed, &candidates)
+///     .into_iter()
+///     .map(|(guess, _)| guess.to_string())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(vec!["frame", "fight", "light", "plows"], ranked);
+/// ```
+pub fn rank_guesses<'a, W: Borrow<Word>>(
+    allowed: &'a [Word],
+    candidates: &[W],
+) -> Vec<(&'a Word, f64)> {
+    let mut ranked = allowed
+        .iter()
+        .map(|guess| (guess, entropy(guess, candidates)))
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+    ranked
+}
+
+/// Ranks the guesses by their [`entropy`] against the words which may still be the solution, from
+/// the most to the least informative. This is the same as [`rank_guesses`], under the name used by
+/// entropy-based solvers.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{rank_by_entropy, rank_guesses, Word};
+/// let guesses = [Word::new("plows"), Word::new("frame")];
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert_eq!(rank_guesses(&guesses, &words), rank_by_entropy(&guesses, &words));
+/// ```
+pub fn rank_by_entropy<'a, W: Borrow<Word>>(
+    candidates: &'a [Word],
+    words: &[W],
+) -> Vec<(&'a Word, f64)> {
+    rank_guesses(candidates, words)
+}
+
+/// Ranks the allowed guesses like [`rank_guesses`], using the constraints the candidates are known
+/// to match to compute fewer hints.
+///
+/// When the constraints lock a letter at a position, every candidate has that letter there, so a
+/// guess with the same letter at that position always gets a green hint for it, which gives no
+/// information. Such positions are left out of the patterns of the guess, which are encoded as
+/// numbers from the letters of the words rather than collected as hints, which speeds up late-game
+/// rankings where most positions are locked. Candidates which do not have the locked letters get
+/// their whole pattern computed, so the ranking is always the same as [`rank_guesses`].
+///
+/// # Panics
+///
+/// Panics if a candidate is not as long as a guess, or if words have more than 40 characters.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{rank_guesses, rank_guesses_with_constraints, solve, Constraints, Hint, Pattern, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("light"), Word::new("flame")];
+/// let pattern = Pattern::from_solution_and_guess(&Word::new("light"), &Word::new("night"));
+/// let candidates = solve(&words, &[(pattern.guess.clone(), pattern.hints.clone())]);
+/// let constraints = Constraints::from_pattern(&pattern);
+///
+/// assert_eq!(
+///     rank_guesses(&words, &candidates),
+///     rank_guesses_with_constraints(&words, &candidates, &constraints)
+/// );
+/// ```
+pub fn rank_guesses_with_constraints<'a, W: Borrow<Word>>(
+    allowed: &'a [Word],
+    candidates: &[W],
+    constraints: &Constraints,
+) -> Vec<(&'a Word, f64)> {
+    let locked = constraints.locked_chars();
+    let (matching, others): (Vec<&Word>, Vec<&Word>) = candidates
+        .iter()
+        .map(Borrow::borrow)
+        .partition(|candidate| {
+            candidate.length() == locked.len()
+                && locked
+                    .iter()
+                    .zip(candidate.chars())
+                    .all(|(locked, c)| locked.is_none_or(|locked| locked == c))
+        });
+
+    let mut ranked = allowed
+        .iter()
+        .map(|guess| {
+            let letters = guess.letters();
+            let chars = guess.chars().collect::<Vec<char>>();
+            let every_position = key_positions(letters, |_| true);
+            let unlocked = key_positions(letters, |i| locked.get(i) != Some(&Some(chars[i])));
+            let mut distribution = HashMap::new();
+
+            for (candidates, (positions, base)) in
+                [(&matching, unlocked), (&others, every_position)]
+            {
+                for candidate in candidates {
+                    let key = base + pattern_key(candidate.letters(), letters, &positions);
+                    *distribution.entry(key).or_insert(0) += 1;
+                }
+            }
+
+            (guess, distribution_entropy(distribution.into_values()))
+        })
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+    ranked
+}
+
+/// Ranks the allowed guesses like [`rank_guesses`], penalizing each letter of a guess which is
+/// known to be absent from the solution by `weight` bits.
+///
+/// Absent letters always get a black hint, so players perceive them as wasted tiles. The penalty
+/// makes the ranking prefer guesses without such letters when they are about as informative as
+/// others. The absent letters are usually those given by [`crate::Solver::absent_letters`], and
+/// [`DEFAULT_ABSENT_LETTER_WEIGHT`] is a sensible weight.
+///
+/// # Examples
+///
+/// ```
+/// # use std::collections::BTreeSet;
+/// # use wools::{rank_guesses_avoiding_absent, Word, DEFAULT_ABSENT_LETTER_WEIGHT};
+/// let allowed = [Word::new("mound"), Word::new("mylar")];
+/// let candidates = [Word::new("fight"), Word::new("might")];
+/// let absent = BTreeSet::from(['o', 'u', 'n', 'd']);
+/// let ranked = rank_guesses_avoiding_absent(&allowed, &candidates, &absent, DEFAULT_ABSENT_LETTER_WEIGHT);
+///
+/// assert_eq!(&Word::new("mylar"), ranked[0].0);
+/// ```
+pub fn rank_guesses_avoiding_absent<'a, W: Borrow<Word>>(
+    allowed: &'a [Word],
+    candidates: &[W],
+    absent: &BTreeSet<char>,
+    weight: f64,
+) -> Vec<(&'a Word, f64)> {
+    let mut ranked = allowed
+        .iter()
+        .map(|guess| {
+            let absent_count = guess.chars().filter(|c| absent.contains(c)).count();
+            (
+                guess,
+                entropy(guess, candidates) - weight * absent_count as f64,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+    ranked
+}
+
+/// Computes the probability that the guess is the solution, i.e. `1 / candidates` if the guess is
+/// one of the candidates and 0 otherwise.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{win_probability, Word};
+/// let candidates = [Word::new("fight"), Word::new("might")];
+///
+/// assert_eq!(0.5, win_probability(&Word::new("fight"), &candidates));
+/// assert_eq!(0.0, win_probability(&Word::new("light"), &candidates));
+/// ```
+pub fn win_probability<W: Borrow<Word>>(guess: &Word, candidates: &[W]) -> f64 {
+    if candidates
+        .iter()
+        .any(|candidate| candidate.borrow() == guess)
+    {
+        1.0 / candidates.len() as f64
+    } else {
+        0.0
+    }
+}
+
+/// Ranks the allowed guesses like [`rank_guesses`], adding `weight` times the [`win_probability`]
+/// of each guess to its entropy.
+///
+/// A guess which is a candidate may end the game right away, which saves a guess without showing
+/// in its entropy. The bonus makes the ranking prefer such guesses when they are about as
+/// informative as others, which lowers the expected number of guesses to finish.
+/// [`DEFAULT_WIN_PROBABILITY_WEIGHT`] is a sensible weight, and a weight of 0 ranks like
+/// [`rank_guesses`].
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{rank_guesses_favoring_candidates, Word, DEFAULT_WIN_PROBABILITY_WEIGHT};
+/// let allowed = [Word::new("mylar"), Word::new("might")];
+/// let candidates = [Word::new("fight"), Word::new("might")];
+/// let ranked = rank_guesses_favoring_candidates(&allowed, &candidates, DEFAULT_WIN_PROBABILITY_WEIGHT);
+///
+/// assert_eq!(&Word::new("might"), ranked[0].0);
+/// ```
+pub fn rank_guesses_favoring_candidates<'a, W: Borrow<Word>>(
+    allowed: &'a [Word],
+    candidates: &[W],
+    weight: f64,
+) -> Vec<(&'a Word, f64)> {
+    let mut ranked = allowed
+        .iter()
+        .map(|guess| {
+            let bonus = weight * win_probability(guess, candidates);
+            (guess, entropy(guess, candidates) + bonus)
+        })
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+    ranked
+}
+
+/// Scores a word by how many candidates have its letters at the same positions, as a cheap
+/// alternative to [`entropy`]. Only the first occurrence of each letter of the word counts, so that
+/// words covering more distinct letters score higher.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{positional_score, Word};
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("sight")];
+///
+/// assert_eq!(8, positional_score(&Word::new("light"), &candidates[..2]));
+/// assert_eq!(13, positional_score(&Word::new("sight"), &candidates));
+/// ```
+pub fn positional_score<W: Borrow<Word>>(word: &Word, candidates: &[W]) -> usize {
+    heatmap_score(word, &candidate_heatmap(candidates))
+}
+
+/// Ranks the allowed guesses by their [`positional_score`], from the highest to the lowest, which
+/// finds good opening guesses much faster than [`rank_guesses`]. The heatmap of the candidates is
+/// only computed once for all guesses. Guesses with equal scores stay in the order of `allowed`.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{rank_by_positional, Word};
+/// let allowed = [Word::new("sissy"), Word::new("stare")];
+/// let candidates = [Word::new("stair"), Word::new("share"), Word::new("spare")];
+///
+/// assert_eq!(&Word::new("stare"), rank_by_positional(&allowed, &candidates)[0].0);
+/// ```
+pub fn rank_by_positional<'a, W: Borrow<Word>>(
+    allowed: &'a [Word],
+    candidates: &[W],
+) -> Vec<(&'a Word, usize)> {
+    let heatmap = candidate_heatmap(candidates);
+    let mut ranked = allowed
+        .iter()
+        .map(|guess| (guess, heatmap_score(guess, &heatmap)))
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(_, first), (_, second)| second.cmp(first));
+    ranked
+}
+
+fn heatmap_score(word: &Word, heatmap: &[Vec<u32>; 26]) -> usize {
+    let mut seen = 0_u32;
+
+    word.letters()
+        .iter()
+        .enumerate()
+        .filter(|(_, &letter)| {
+            let first = seen & 1 << letter == 0;
+            seen |= 1 << letter;
+            first
+        })
+        .filter_map(|(i, &letter)| heatmap.get(letter as usize)?.get(i).copied())
+        .map(|count| count as usize)
+        .sum()
+}
+
+/// The number of characters of the longest words whose patterns fit in the `u64` keys of
+/// [`pattern_key`], since 3⁴⁰ < 2⁶⁴.
+const MAX_KEY_LENGTH: usize = 40;
+
+/// Returns the positions of the guess kept by the filter along with the weight of their hint in a
+/// pattern key, and the key of the other positions, which are all green.
+fn key_positions<F: Fn(usize) -> bool>(letters: &[u8], keep: F) -> (Vec<(usize, u64)>, u64) {
+    assert!(
+        letters.len() <= MAX_KEY_LENGTH,
+        "patterns of more than {} hints cannot be keyed",
+        MAX_KEY_LENGTH
+    );
+
+    let mut positions = Vec::new();
+    let mut base = 0;
+
+    for (i, weight) in (0..letters.len()).zip(std::iter::successors(Some(1), |w| Some(w * 3))) {
+        if keep(i) {
+            positions.push((i, weight));
+        } else {
+            base += 2 * weight;
+        }
+    }
+
+    (positions, base)
+}
+
+/// Encodes the hints of the guess against the solution at the positions, as their weights times 0
+/// for black, 1 for yellow and 2 for green. Left out positions must hold the same letter in the
+/// guess and the solution, since they do not use up any letter of the solution.
+fn pattern_key(solution: &[u8], guess: &[u8], positions: &[(usize, u64)]) -> u64 {
+    assert_eq!(
+        solution.len(),
+        guess.len(),
+        "guess and solution have different lengths"
+    );
+
+    let mut counts = [0_u8; Alphabet::MAX_LETTERS];
+
+    for &(i, _) in positions {
+        if guess[i] != solution[i] {
+            counts[solution[i] as usize] += 1;
+        }
+    }
+
+    positions.iter().fold(0, |key, &(i, weight)| {
+        let count = &mut counts[guess[i] as usize];
+
+        key + weight
+            * if guess[i] == solution[i] {
+                2
+            } else if *count > 0 {
+                *count -= 1;
+                1
+            } else {
+                0
+            }
+    })
+}
+
+/// Recommends the next guess, switching strategy depending on how many candidates remain.
+///
+/// When there are `threshold` candidates or fewer, gathering information is wasteful, so the
+/// first candidate is guessed in the hope of winning right away. Otherwise, the most informative
+/// allowed guess is recommended (see [`rank_guesses`]). Returns `None` when there is nothing to
+/// guess. [`DEFAULT_ENDGAME_THRESHOLD`] is a sensible threshold.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{best_guess_adaptive, Word, DEFAULT_ENDGAME_THRESHOLD};
+/// let allowed = [Word::new("pouty"), Word::new("frame"), Word::new("fight")];
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert_eq!(
+///     Some(&Word::new("frame")),
+///     best_guess_adaptive(&allowed, &candidates, DEFAULT_ENDGAME_THRESHOLD)
+/// );
+/// assert_eq!(
+///     Some(&Word::new("fight")),
+///     best_guess_adaptive(&allowed, &candidates[..2], DEFAULT_ENDGAME_THRESHOLD)
+/// );
+/// ```
+pub fn best_guess_adaptive<'a, W: Borrow<Word>>(
+    allowed: &'a [Word],
+    candidates: &'a [W],
+    threshold: usize,
+) -> Option<&'a Word> {
+    if candidates.len() <= threshold {
+        candidates.first().map(Borrow::borrow)
+    } else {
+        rank_guesses(allowed, candidates)
+            .first()
+            .map(|(guess, _)| *guess)
+    }
+}
+
+/// Ranks the allowed guesses by how many candidates they leave according to the scorer, from the
+/// fewest to the most. Guesses with equal scores are kept in the order of `allowed`.
+///
+/// The scorer is usually [`expected_remaining`], to minimize the number of candidates left on
+/// average, or [`worst_case_remaining`], to minimize it in the worst case (minimax).
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{best_guess, expected_remaining, worst_case_remaining, Word};
+/// let allowed = [Word::new("light"), Word::new("frame")];
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert_eq!(&Word::new("frame"), best_guess(&allowed, &candidates, expected_remaining)[0].0);
+/// assert_eq!(
+///     vec![(&Word::new("frame"), 1.0), (&Word::new("light"), 2.0)],
+///     best_guess(&allowed, &candidates, worst_case_remaining)
+/// );
+/// ```
+pub fn best_guess<'a, W, F>(allowed: &'a [Word], candidates: &[W], score: F) -> Vec<(&'a Word, f64)>
+where
+    W: Borrow<Word>,
+    F: Fn(&Word, &[W]) -> f64,
+{
+    let mut ranked = allowed
+        .iter()
+        .map(|guess| (guess, score(guess, candidates)))
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(_, first), (_, second)| first.total_cmp(second));
+    ranked
+}
+
+/// Ranks the allowed guesses like [`best_guess`], scoring them on every available thread. The
+/// ranking is the same as the one of [`best_guess`], including the order of guesses with equal
+/// scores.
+///
+/// This requires the `rayon` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{best_guess, best_guess_parallel, expected_remaining, Word};
+/// let allowed = [Word::new("light"), Word::new("frame"), Word::new("might")];
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert_eq!(
+///     best_guess(&allowed, &candidates, expected_remaining),
+///     best_guess_parallel(&allowed, &candidates, expected_remaining)
+/// );
+/// ```
+#[cfg(feature = "rayon")]
+pub fn best_guess_parallel<'a, W, F>(
+    allowed: &'a [Word],
+    candidates: &[W],
+    score: F,
+) -> Vec<(&'a Word, f64)>
+where
+    W: Borrow<Word> + Sync,
+    F: Fn(&Word, &[W]) -> f64 + Sync,
+{
+    use rayon::prelude::*;
+
+    // Collecting keeps the order of `allowed`, so the stable sort breaks ties like `best_guess`.
+    let mut ranked = allowed
+        .par_iter()
+        .map(|guess| (guess, score(guess, candidates)))
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(_, first), (_, second)| first.total_cmp(second));
+    ranked
+}
+
+/// Recommends the allowed guess leaving the fewest candidates on average (see
+/// [`expected_remaining`]), which is an alternative to maximizing the entropy. Guesses leaving as
+/// many candidates are picked in the order of `allowed`. Returns `None` when `allowed` is empty.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{best_guess_expected_remaining, Word};
+/// let allowed = [Word::new("light"), Word::new("frame")];
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert_eq!(Some(&Word::new("frame")), best_guess_expected_remaining(&allowed, &candidates));
+/// ```
+pub fn best_guess_expected_remaining<'a, W: Borrow<Word>>(
+    allowed: &'a [Word],
+    candidates: &[W],
+) -> Option<&'a Word> {
+    best_guess(allowed, candidates, expected_remaining)
+        .first()
+        .map(|(guess, _)| *guess)
+}
+
+/// Ranks the allowed guesses by the number of candidates they leave in the worst case (see
+/// [`worst_case_remaining`]), from the fewest to the most. Among guesses leaving as many
+/// candidates, the ones which may be the solution come first, since they may win right away, then
+/// guesses are kept in the order of `allowed`.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{rank_minimax, Word};
+/// let allowed = [Word::new("frame"), Word::new("light"), Word::new("fight")];
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert_eq!(
+///     vec![(&allowed[2], 1), (&allowed[0], 1), (&allowed[1], 2)],
+///     rank_minimax(&allowed, &candidates)
+/// );
+/// ```
+pub fn rank_minimax<'a, W: Borrow<Word>>(
+    allowed: &'a [Word],
+    candidates: &[W],
+) -> Vec<(&'a Word, usize)> {
+    let possible = candidates
+        .iter()
+        .map(Borrow::borrow)
+        .collect::<HashSet<&Word>>();
+    let mut ranked = allowed
+        .iter()
+        .map(|guess| (guess, worst_case_remaining(guess, candidates) as usize))
+        .collect::<Vec<_>>();
+
+    ranked.sort_by_key(|(guess, remaining)| (*remaining, !possible.contains(guess)));
+    ranked
+}
+
+/// Recommends the allowed guess leaving the fewest candidates in the worst case, along with that
+/// number of candidates, preferring guesses which may be the solution like [`rank_minimax`] does.
+/// Returns `None` when `allowed` is empty.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{minimax_guess, Word};
+/// let allowed = [Word::new("light"), Word::new("frame")];
+/// let candidates = [Word::new("fight"), Word::new("might"), Word::new("crane")];
+///
+/// assert_eq!(Some((&Word::new("frame"), 1)), minimax_guess(&allowed, &candidates));
+/// ```
+pub fn minimax_guess<'a, W: Borrow<Word>>(
+    allowed: &'a [Word],
+    candidates: &[W],
+) -> Option<(&'a Word, usize)> {
+    rank_minimax(allowed, candidates).first().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::pattern::Hint::{Black, Green, Yellow};
+    use crate::strategy::{
+        best_guess, best_guess_adaptive, best_guess_expected_remaining, entropy,
+        expected_remaining, minimax_guess, partition, pattern_distribution, positional_score,
+        rank_by_positional, rank_guesses, rank_guesses_avoiding_absent,
+        rank_guesses_favoring_candidates, rank_guesses_with_constraints, rank_minimax,
+        win_probability, worst_case_remaining,
+    };
+    use crate::{load_default_words, solve, Constraints, Pattern, Word};
+
+    fn words(words: &[&str]) -> Vec<Word> {
+        words.iter().map(|word| Word::new(word)).collect()
+    }
+
+    #[test]
+    fn given_guess_has_a_triple_letter_when_pattern_distribution_then_candidates_are_bucketed_by_their_own_counts(
+    ) {
+        let candidates = words(&["madam", "momma", "llama", "drama", "gamma", "mamma"]);
+        let distribution = pattern_distribution(&Word::new("mamma"), &candidates);
+
+        assert_eq!(5, distribution.len());
+        assert_eq!(1, distribution[&vec![Green, Green, Yellow, Black, Yellow]]);
+        assert_eq!(1, distribution[&vec![Green, Black, Green, Green, Green]]);
+        assert_eq!(2, distribution[&vec![Black, Yellow, Black, Green, Green]]);
+        assert_eq!(1, distribution[&vec![Black, Green, Green, Green, Green]]);
+        assert_eq!(1, distribution[&vec![Green; Word::SIZE]]);
+    }
+
+    #[test]
+    fn given_guess_has_doubled_letters_when_pattern_distribution_then_candidates_are_bucketed_by_their_own_counts(
+    ) {
+        let candidates = words(&["geese", "eerie", "sassy", "seeds", "needs"]);
+        let distribution = pattern_distribution(&Word::new("seeds"), &candidates);
+
+        assert_eq!(5, distribution.len());
+        assert_eq!(1, distribution[&vec![Yellow, Green, Green, Black, Black]]);
+        assert_eq!(1, distribution[&vec![Black, Green, Yellow, Black, Black]]);
+        assert_eq!(1, distribution[&vec![Green, Black, Black, Black, Yellow]]);
+        assert_eq!(1, distribution[&vec![Black, Green, Green, Green, Green]]);
+        assert_eq!(1, distribution[&vec![Green; Word::SIZE]]);
+    }
+
+    #[test]
+    fn given_guess_has_a_quadruple_letter_when_pattern_distribution_then_only_solution_count_is_yellow(
+    ) {
+        let candidates = words(&["bbbba", "abbbb", "aabbb"]);
+        let distribution = pattern_distribution(&Word::new("aaaab"), &candidates);
+
+        assert_eq!(1, distribution[&vec![Yellow, Black, Black, Black, Yellow]]);
+        assert_eq!(1, distribution[&vec![Green, Black, Black, Black, Green]]);
+        assert_eq!(1, distribution[&vec![Green, Green, Black, Black, Green]]);
+    }
+
+    #[test]
+    fn given_guess_splits_candidates_evenly_when_entropy_then_return_bits_of_information() {
+        let candidates = words(&["fight", "might", "light", "sight"]);
+
+        assert_eq!(2.0, entropy(&Word::new("flams"), &candidates));
+        assert_eq!(0.0, entropy(&Word::new("crane"), &candidates));
+    }
+
+    #[test]
+    fn given_no_candidates_when_entropy_then_return_zero() {
+        assert_eq!(0.0, entropy::<Word>(&Word::new("crane"), &[]));
+    }
+
+    #[test]
+    fn when_rank_guesses_then_guesses_are_sorted_by_descending_entropy_then_by_order() {
+        let allowed = words(&["crane", "flams", "light", "sight"]);
+        let candidates = words(&["fight", "might", "light", "sight"]);
+        let ranked = rank_guesses(&allowed, &candidates)
+            .into_iter()
+            .map(|(guess, _)| guess.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["flams", "light", "sight", "crane"], ranked);
+    }
+
+    #[test]
+    fn given_locked_positions_when_rank_guesses_with_constraints_then_ranking_is_the_same() {
+        let words = load_default_words();
+        let allowed = &words[..2000];
+
+        for (solution, guess) in [("light", "night"), ("mourn", "morns"), ("tonal", "total")] {
+            let pattern = Pattern::from_solution_and_guess(&Word::new(solution), &Word::new(guess));
+            let constraints = Constraints::from_pattern(&pattern);
+            let candidates = solve(&words, &[(pattern.guess.clone(), pattern.hints)]);
+
+            assert_eq!(
+                rank_guesses(allowed, &candidates),
+                rank_guesses_with_constraints(allowed, &candidates, &constraints)
+            );
+        }
+    }
+
+    #[test]
+    fn given_candidates_without_locked_letters_when_rank_guesses_with_constraints_then_ranking_is_the_same(
+    ) {
+        let allowed = words(&["fight", "crane", "nymph", "light"]);
+        let candidates = words(&["light", "might", "crane", "tonal"]);
+        let constraints = Constraints::from_pattern(&Pattern::from_solution_and_guess(
+            &Word::new("light"),
+            &Word::new("night"),
+        ));
+
+        assert_eq!(
+            rank_guesses(&allowed, &candidates),
+            rank_guesses_with_constraints(&allowed, &candidates, &constraints)
+        );
+    }
+
+    #[test]
+    fn given_equal_entropy_guesses_when_rank_guesses_avoiding_absent_then_penalized_guess_is_last()
+    {
+        let allowed = words(&["mound", "mylar"]);
+        let candidates = words(&["fight", "might"]);
+        let absent = BTreeSet::from(['o', 'u', 'n', 'd']);
+
+        assert_eq!(&allowed[0], rank_guesses(&allowed, &candidates)[0].0);
+
+        let ranked = rank_guesses_avoiding_absent(&allowed, &candidates, &absent, 0.01);
+
+        assert_eq!(&allowed[1], ranked[0].0);
+        assert_eq!(&allowed[0], ranked[1].0);
+        assert!((ranked[0].1 - 1.0).abs() < 1e-9);
+        assert!((ranked[1].1 - 0.96).abs() < 1e-9);
+    }
+
+    #[test]
+    fn given_no_weight_when_rank_guesses_avoiding_absent_then_ranking_is_the_same() {
+        let allowed = words(&["mound", "mylar", "crane"]);
+        let candidates = words(&["fight", "might", "crane"]);
+        let absent = BTreeSet::from(['o', 'u', 'n', 'd']);
+
+        assert_eq!(
+            rank_guesses(&allowed, &candidates),
+            rank_guesses_avoiding_absent(&allowed, &candidates, &absent, 0.0)
+        );
+    }
+
+    #[test]
+    fn given_candidates_above_threshold_when_best_guess_adaptive_then_return_most_informative_guess(
+    ) {
+        let allowed = words(&["crane", "flams"]);
+        let candidates = words(&["fight", "might", "light"]);
+
+        assert_eq!(
+            Some(&Word::new("flams")),
+            best_guess_adaptive(&allowed, &candidates, 2)
+        );
+    }
+
+    #[test]
+    fn given_candidates_at_threshold_when_best_guess_adaptive_then_return_a_candidate() {
+        let allowed = words(&["crane", "flams"]);
+        let candidates = words(&["fight", "might", "light"]);
+
+        assert_eq!(
+            Some(&Word::new("fight")),
+            best_guess_adaptive(&allowed, &candidates, 3)
+        );
+        assert_eq!(
+            Some(&Word::new("fight")),
+            best_guess_adaptive(&allowed, &candidates[..2], 2)
+        );
+    }
+
+    #[test]
+    fn given_no_candidates_when_best_guess_adaptive_then_return_none() {
+        let allowed = words(&["crane", "flams"]);
+
+        assert_eq!(None, best_guess_adaptive::<Word>(&allowed, &[], 2));
+    }
+
+    #[test]
+    fn when_expected_remaining_then_return_average_group_size_of_solution() {
+        let candidates = words(&["fight", "might", "light", "sight", "crane"]);
+
+        // crane splits off crane and leaves the four -ight words together: (16 + 1) / 5.
+        assert!((expected_remaining(&Word::new("crane"), &candidates) - 17.0 / 5.0).abs() < 1e-9);
+        assert!((expected_remaining(&Word::new("fight"), &candidates) - 11.0 / 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn given_no_candidates_when_expected_remaining_then_return_zero() {
+        assert_eq!(0.0, expected_remaining::<Word>(&Word::new("crane"), &[]));
+    }
+
+    #[test]
+    fn when_best_guess_expected_remaining_then_return_guess_leaving_fewest_candidates() {
+        let allowed = words(&["crane", "fight", "moist"]);
+        let candidates = words(&["fight", "might", "light", "sight", "crane"]);
+
+        assert_eq!(
+            Some(&allowed[2]),
+            best_guess_expected_remaining(&allowed, &candidates)
+        );
+    }
+
+    #[test]
+    fn given_equal_worst_cases_when_rank_minimax_then_possible_solutions_come_first() {
+        let candidates = words(&["fight", "might", "light", "sight", "crane"]);
+        let allowed = words(&["plumb", "flame", "fight", "sight"]);
+
+        assert_eq!(
+            vec![
+                (&allowed[1], 1),
+                (&allowed[2], 3),
+                (&allowed[3], 3),
+                (&allowed[0], 3)
+            ],
+            rank_minimax(&allowed, &candidates)
+        );
+        assert_eq!(Some((&allowed[1], 1)), minimax_guess(&allowed, &candidates));
+        assert_eq!(None, minimax_guess(&[], &candidates));
+    }
+
+    #[test]
+    fn given_no_allowed_guesses_when_best_guess_expected_remaining_then_return_none() {
+        assert_eq!(None, best_guess_expected_remaining(&[], &words(&["fight"])));
+    }
+
+    #[test]
+    fn given_equal_entropy_guesses_when_rank_guesses_favoring_candidates_then_candidate_is_first() {
+        let allowed = words(&["mylar", "might"]);
+        let candidates = words(&["fight", "might"]);
+
+        assert_eq!(&allowed[0], rank_guesses(&allowed, &candidates)[0].0);
+
+        let ranked = rank_guesses_favoring_candidates(&allowed, &candidates, 0.1);
+
+        assert_eq!(&allowed[1], ranked[0].0);
+        assert!((ranked[0].1 - 1.05).abs() < 1e-9);
+        assert!((ranked[1].1 - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn given_more_informative_guess_when_rank_guesses_favoring_candidates_then_bonus_does_not_win()
+    {
+        let allowed = words(&["fight", "moist"]);
+        let candidates = words(&["fight", "might", "light", "sight", "crane"]);
+        let ranked = rank_guesses_favoring_candidates(&allowed, &candidates, 0.1);
+
+        assert_eq!(&allowed[1], ranked[0].0);
+    }
+
+    #[test]
+    fn given_empty_candidates_when_win_probability_then_return_zero() {
+        assert_eq!(0.0, win_probability::<Word>(&Word::new("fight"), &[]));
+    }
+
+    #[test]
+    fn when_best_guess_then_guesses_are_sorted_by_fewest_remaining_candidates() {
+        let allowed = words(&["crane", "fight", "moist"]);
+        let candidates = words(&["fight", "might", "light", "sight", "crane"]);
+        let average = best_guess(&allowed, &candidates, expected_remaining);
+        let minimax = best_guess(&allowed, &candidates, worst_case_remaining);
+
+        assert_eq!(
+            vec![&allowed[2], &allowed[1], &allowed[0]],
+            average.iter().map(|(guess, _)| *guess).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(&allowed[2], 2.0), (&allowed[1], 3.0), (&allowed[0], 4.0)],
+            minimax
+        );
+    }
+
+    #[test]
+    fn given_equal_scores_when_best_guess_then_guesses_are_in_allowed_order() {
+        let allowed = words(&["stare", "crane"]);
+        let candidates = words(&["fight", "might"]);
+
+        assert_eq!(
+            vec![(&allowed[0], 2.0), (&allowed[1], 2.0)],
+            best_guess(&allowed, &candidates, worst_case_remaining)
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn when_best_guess_parallel_then_ranking_is_the_same_as_best_guess() {
+        let words = load_default_words();
+        let allowed = words.iter().step_by(50).cloned().collect::<Vec<Word>>();
+        let candidates = words.iter().step_by(40).collect::<Vec<&Word>>();
+
+        assert_eq!(
+            best_guess(&allowed, &candidates, worst_case_remaining),
+            crate::strategy::best_guess_parallel(&allowed, &candidates, worst_case_remaining)
+        );
+    }
+
+    #[test]
+    fn given_repeated_letters_when_positional_score_then_count_each_letter_once() {
+        let candidates = words(&["sissy", "sassy", "mossy"]);
+
+        assert_eq!(2, positional_score(&Word::new("sssss"), &candidates));
+        assert_eq!(
+            2 + 1 + 3,
+            positional_score(&Word::new("sissy"), &candidates)
+        );
+    }
+
+    #[test]
+    fn given_high_coverage_word_when_rank_by_positional_then_it_outranks_repeated_letters() {
+        let allowed = words(&["eerie", "slate", "sassy"]);
+        let candidates = load_default_words();
+        let ranked = rank_by_positional(&allowed, &candidates);
+
+        assert_eq!(&Word::new("slate"), ranked[0].0);
+        assert_eq!(
+            positional_score(&Word::new("slate"), &candidates),
+            ranked[0].1
+        );
+    }
+
+    #[test]
+    fn when_partition_then_bucket_sizes_sum_to_word_count() {
+        let words = load_default_words();
+        let guess = Word::new("crane");
+        let partition = partition(&guess, &words);
+
+        assert_eq!(words.len(), partition.values().map(Vec::len).sum::<usize>());
+        assert!(partition.len() <= 3_usize.pow(Word::SIZE as u32));
+        assert!(partition.values().all(|bucket| !bucket.is_empty()));
+        assert_eq!(vec![&guess], partition[&vec![Green; Word::SIZE]]);
+
+        let distribution = pattern_distribution(&guess, &words);
+        for (hints, bucket) in &partition {
+            assert_eq!(distribution[hints], bucket.len());
+        }
+    }
+}
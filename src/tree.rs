@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use crate::error::WordsError;
+use crate::pattern::{code, Hint, Pattern, MAX_CODE_LENGTH};
+use crate::strategy::{best_guess_adaptive, DEFAULT_ENDGAME_THRESHOLD};
+use crate::word::Word;
+
+const MAGIC: &[u8; 4] = b"WTRE";
+const VERSION: u8 = 3;
+
+/// A decision tree telling which word to guess next for every pattern obtained so far, down to
+/// the solution, so that a game can be played without any search.
+///
+/// # Format
+///
+/// A tree is written as a header followed by its root node. The header is made of the `WTRE`
+/// magic bytes, a format version byte (currently `3`) and a byte holding the length of the words.
+/// A node is made of its guess, written as a byte holding its length in bytes, its UTF-8 bytes and
+/// the index of each of its letters as a byte (see [`Word::letters`]), its number of children as a
+/// little-endian `u16`, then each child as its pattern code, a little-endian `u16`, followed by
+/// the child node, by ascending pattern code (see [`Pattern::to_code`]). The all-green pattern never has a child.
+///
+/// # Examples
+///
+/// ```
+/// # use wools::{DecisionTree, Pattern, Word};
+/// let words = [Word::new("fight"), Word::new("might"), Word::new("crane"), Word::new("plumb")];
+/// let tree = DecisionTree::build(&words, &Word::new("might"));
+/// let solution = Word::new("plumb");
+///
+/// let first = Pattern::from_solution_and_guess(&solution, tree.guess());
+/// let next = tree.next(&first.hints).unwrap();
+/// assert!(Pattern::from_solution_and_guess(&solution, next.guess()).is_solved());
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct DecisionTree {
+    guess: Word,
+    children: BTreeMap<u16, DecisionTree>,
+}
+
+impl DecisionTree {
+    /// Builds the tree of every game starting with the opener, the solution being any of the words.
+    ///
+    /// After each guess, the next one is picked among the remaining candidates like
+    /// [`best_guess_adaptive`] does, so that every game eventually ends. This computes patterns
+    /// for every pair of candidates at every node, which takes a while for large dictionaries.
+    /// Words which are not as long as the opener are left out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the opener has more than 10 characters.
+    pub fn build(words: &[Word], opener: &Word) -> Self {
+        assert!(
+            opener.length() <= MAX_CODE_LENGTH,
+            "decision trees only support words of at most {} characters",
+            MAX_CODE_LENGTH
+        );
+
+        let candidates = words
+            .iter()
+            .filter(|word| word.length() == opener.length())
+            .collect();
+        DecisionTree::build_node(opener, candidates)
+    }
+
+    fn build_node(guess: &Word, candidates: Vec<&Word>) -> Self {
+        let mut buckets: BTreeMap<u16, Vec<Word>> = BTreeMap::new();
+
+        for candidate in candidates {
+            let pattern = Pattern::from_solution_and_guess(candidate, guess);
+
+            if !pattern.is_solved() {
+                buckets
+                    .entry(pattern.to_code())
+                    .or_default()
+                    .push(candidate.clone());
+            }
+        }
+
+        let children = buckets
+            .into_iter()
+            .map(|(code, bucket)| {
+                let response = best_guess_adaptive(&bucket, &bucket, DEFAULT_ENDGAME_THRESHOLD)
+                    .unwrap()
+                    .clone();
+                (
+                    code,
+                    DecisionTree::build_node(&response, bucket.iter().collect()),
+                )
+            })
+            .collect();
+
+        DecisionTree {
+            guess: guess.clone(),
+            children,
+        }
+    }
+
+    /// Returns the word to guess at this point of the game.
+    pub fn guess(&self) -> &Word {
+        &self.guess
+    }
+
+    /// Returns the tree to follow once the guess produced the hints, or `None` if the hints solve
+    /// the game or cannot be produced by any of the words.
+    pub fn next(&self, hints: &[Hint]) -> Option<&DecisionTree> {
+        self.children.get(&code(hints))
+    }
+
+    /// Writes the tree in the format described in [`DecisionTree`].
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION, self.guess.length() as u8])?;
+        self.write_node(writer)
+    }
+
+    fn write_node<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.guess.write_to(writer)?;
+        writer.write_all(&(self.children.len() as u16).to_le_bytes())?;
+
+        for (code, child) in &self.children {
+            writer.write_all(&code.to_le_bytes())?;
+            child.write_node(writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a tree written in the format described in [`DecisionTree`].
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, String> {
+        let mut header = [0; 6];
+        reader
+            .read_exact(&mut header)
+            .map_err(|err| err.to_string())?;
+
+        if &header[..4] != MAGIC {
+            return Err("not a decision tree".to_string());
+        } else if header[4] != VERSION {
+            return Err(format!("unsupported decision tree version {}", header[4]));
+        } else if header[5] as usize > MAX_CODE_LENGTH {
+            return Err(format!("unsupported word length {}", header[5]));
+        }
+
+        DecisionTree::read_node(reader, header[5] as usize)
+    }
+
+    fn read_node<R: Read>(reader: &mut R, length: usize) -> Result<Self, String> {
+        let guess = Word::read_from(reader)?;
+        let mut count = [0; 2];
+        reader
+            .read_exact(&mut count)
+            .map_err(|err| err.to_string())?;
+
+        if guess.length() != length {
+            return Err(WordsError::WrongLength {
+                expected: length,
+                actual: guess.length(),
+            }
+            .to_string());
+        }
+        let mut children = BTreeMap::new();
+
+        for _ in 0..u16::from_le_bytes(count) {
+            let mut code = [0; 2];
+            reader
+                .read_exact(&mut code)
+                .map_err(|err| err.to_string())?;
+            children.insert(
+                u16::from_le_bytes(code),
+                DecisionTree::read_node(reader, length)?,
+            );
+        }
+
+        Ok(DecisionTree { guess, children })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::DecisionTree;
+    use crate::{Alphabet, Pattern, Word};
+
+    fn words() -> Vec<Word> {
+        [
+            "fight", "might", "light", "sight", "crane", "plumb", "stare", "tears", "rates",
+        ]
+        .into_iter()
+        .map(Word::new)
+        .collect()
+    }
+
+    #[test]
+    fn when_build_then_following_the_tree_solves_every_word() {
+        let words = words();
+        let tree = DecisionTree::build(&words, &Word::new("crane"));
+
+        for solution in &words {
+            let mut node = &tree;
+            let mut pattern = Pattern::from_solution_and_guess(solution, node.guess());
+
+            while !pattern.is_solved() {
+                node = node.next(&pattern.hints).unwrap();
+                pattern = Pattern::from_solution_and_guess(solution, node.guess());
+            }
+        }
+    }
+
+    #[test]
+    fn when_write_to_and_read_from_then_tree_is_the_same() {
+        let tree = DecisionTree::build(&words(), &Word::new("crane"));
+        let mut bytes = Vec::new();
+
+        tree.write_to(&mut bytes).unwrap();
+
+        assert_eq!(b"WTRE\x03\x05\x05crane\x02\x11\x00\x0d\x04", &bytes[..17]);
+        assert_eq!(
+            tree,
+            DecisionTree::read_from(&mut bytes.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn given_bytes_are_not_a_tree_when_read_from_then_return_error() {
+        assert!(DecisionTree::read_from(
+            &mut b"WTRX\x03\x05\x05crane\x02\x11\x00\x0d\x04\x00".as_slice()
+        )
+        .is_err());
+        assert!(DecisionTree::read_from(&mut b"WTRE\x03\x05\x05cra".as_slice()).is_err());
+    }
+
+    #[test]
+    fn given_longer_words_when_write_to_and_read_from_then_tree_is_the_same() {
+        let words = ["plumbs", "flight", "tights", "sights"]
+            .into_iter()
+            .map(|word| Word::with_length(word, 6).unwrap())
+            .collect::<Vec<_>>();
+        let tree = DecisionTree::build(&words, &words[1]);
+        let mut bytes = Vec::new();
+
+        tree.write_to(&mut bytes).unwrap();
+
+        assert_eq!(b"WTRE\x03\x06\x06flight", &bytes[..13]);
+        assert_eq!(
+            tree,
+            DecisionTree::read_from(&mut bytes.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn given_words_of_another_alphabet_when_write_to_and_read_from_then_tree_is_the_same() {
+        let russian = Alphabet::new("абвгдежзийклмнопрстуфхцчшщъыьэюя".chars()).unwrap();
+        let words = ["книга", "слово", "почта", "школа"]
+            .map(|word| Word::with_alphabet(word, 5, &russian).unwrap());
+        let tree = DecisionTree::build(&words, &words[0]);
+        let mut bytes = Vec::new();
+
+        tree.write_to(&mut bytes).unwrap();
+
+        assert_eq!(b"WTRE\x03\x05\x0a", &bytes[..7]);
+        assert_eq!(
+            tree,
+            DecisionTree::read_from(&mut bytes.as_slice()).unwrap()
+        );
+    }
+}
@@ -1,20 +1,48 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
 use std::str::FromStr;
 
-/// A word for which its length is strictly defined as [`Word::SIZE`], and for which characters are
-/// alphabetical and normalized.
-#[derive(Clone, Debug, PartialEq)]
+use crate::alphabet::Alphabet;
+use crate::error::WordsError;
+use crate::pattern::{Hint, Pattern};
+
+/// A word for which characters are alphabetical and normalized. Words have [`Word::SIZE`]
+/// characters by default, but may have any other length (see [`Word::with_length`]).
+///
+/// Words which may not be valid, such as user input, are created with [`Word::try_new`] or
+/// [`Word::from_str`], while [`Word::new`] panics on invalid words.
+///
+/// The length is known at runtime rather than being a const parameter of the type, so that words
+/// read from a dictionary, whose length is only known once read, share a single type, along with
+/// the patterns, constraints and strategies working on them.
+///
+/// Letters are the 26 letters from `a` to `z` by default, one per tile, and other alphabets of up
+/// to 32 letters, such as Spanish or Russian, are supported through [`Word::with_alphabet`]. Words
+/// are stored as letter indices and sets of letters as 32-bit masks, so comparing words of
+/// different alphabets, e.g. when computing patterns, is not supported. Tiles made of several
+/// characters, such as the Spanish `ll`, cannot be represented (see [`Alphabet`]).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Word {
     word: String,
+    letters: Vec<u8>,
+    mask: u32,
 }
 
 impl Word {
-    /// The size that each word must have, in unicode scalar value count.
+    /// The size that each word has by default, in unicode scalar value count.
     pub const SIZE: usize = 5;
 
     /// Creates a new word from a string, or panics if it cannot.
     ///
-    /// For more information, see [`Word::from_str`].
+    /// This is meant for words known to be valid, such as literals in tests and examples. Words
+    /// read from users or files should be created with [`Word::try_new`] instead, which returns an
+    /// error rather than panicking. For more information, see [`Word::from_str`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the word is not valid.
     ///
     /// # Examples
     ///
@@ -26,21 +54,493 @@ impl Word {
         Word::from_str(word).unwrap()
     }
 
+    /// Creates a new word from a string, or returns an error if it cannot. This is the same as
+    /// [`Word::from_str`], and the non-panicking alternative to [`Word::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Word, WordsError};
+    /// assert_eq!(Ok(Word::new("saute")), Word::try_new("sauté"));
+    /// assert_eq!(Err(WordsError::NonAlphabetic), Word::try_new("bob's"));
+    /// ```
+    pub fn try_new(word: &str) -> Result<Self, WordsError> {
+        Word::from_str(word)
+    }
+
+    /// Creates a new word from a string which must have `length` characters, such as for Wordle
+    /// variants played with 4-letter or 6-letter words. Otherwise, behaves like [`Word::from_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// let word = Word::with_length("potato", 6).unwrap();
+    ///
+    /// assert_eq!(6, word.length());
+    /// assert!(Word::with_length("potato", 5).is_err());
+    /// ```
+    pub fn with_length(word: &str, length: usize) -> Result<Self, WordsError> {
+        let word = word.trim();
+        let actual = word.chars().count();
+
+        if actual != length {
+            return Err(WordsError::WrongLength {
+                expected: length,
+                actual,
+            });
+        }
+
+        let word = word
+            .to_lowercase()
+            .chars()
+            .map(transliterate)
+            .collect::<String>();
+
+        if word.chars().all(|c| c.is_ascii_lowercase()) {
+            Ok(Word::from_normalized(word))
+        } else {
+            Err(WordsError::NonAlphabetic)
+        }
+    }
+
+    /// Creates a new word from a string which must have `length` letters of the alphabet, once
+    /// lowercased. Unlike [`Word::with_length`], characters are not transliterated, so accented
+    /// letters must either be part of the alphabet or be replaced beforehand. The characters of
+    /// the word, as given by [`Word::chars`], are the letters of the alphabet it is made of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Alphabet, Word, WordsError};
+    /// let russian = Alphabet::new("абвгдежзийклмнопрстуфхцчшщъыьэюя".chars()).unwrap();
+    /// let word = Word::with_alphabet("Книга", 5, &russian).unwrap();
+    ///
+    /// assert_eq!("книга", word.as_str());
+    /// assert_eq!(Some('г'), word.chars().nth(3));
+    /// assert!(word.reveal_against(&word).is_solved());
+    /// assert_eq!(Err(WordsError::NonAlphabetic), Word::with_alphabet("kniga", 5, &russian));
+    /// ```
+    pub fn with_alphabet(
+        word: &str,
+        length: usize,
+        alphabet: &Alphabet,
+    ) -> Result<Self, WordsError> {
+        let word = word.trim().to_lowercase();
+        let actual = word.chars().count();
+
+        if actual != length {
+            return Err(WordsError::WrongLength {
+                expected: length,
+                actual,
+            });
+        }
+
+        let letters = word
+            .chars()
+            .map(|c| alphabet.letter(c).ok_or(WordsError::NonAlphabetic))
+            .collect::<Result<Vec<u8>, WordsError>>()?;
+
+        Ok(Word::from_letters(word, letters))
+    }
+
+    /// Creates a new word from a string, replacing characters using a custom transliteration table
+    /// before the length is checked, so that a character may be replaced by several others, or by
+    /// none. The string is lowercased before the replacements, so the table only needs lowercase
+    /// characters. Characters which are not in the table are converted like [`Word::from_str`]
+    /// does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use wools::Word;
+    /// let table = HashMap::from([('ß', "ss"), ('ø', "o"), ('å', "a")]);
+    ///
+    /// assert_eq!("masse", Word::from_str_with_map("MAßE", &table).unwrap().as_str());
+    /// assert_eq!("blode", Word::from_str_with_map("bløde", &table).unwrap().as_str());
+    /// assert!(Word::from_str_with_map("straße", &table).is_err());
+    /// ```
+    pub fn from_str_with_map(word: &str, table: &HashMap<char, &str>) -> Result<Self, WordsError> {
+        let word = word
+            .to_lowercase()
+            .chars()
+            .map(|c| match table.get(&c) {
+                Some(replacement) => replacement.to_string(),
+                None => transliterate(c).to_string(),
+            })
+            .collect::<String>();
+
+        Word::with_length(&word, Word::SIZE)
+    }
+
+    /// Returns the number of characters of the word.
+    pub fn length(&self) -> usize {
+        self.letters.len()
+    }
+
+    /// Returns the normalized word as a string slice, without allocating. This allows lookups in
+    /// collections of strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::collections::HashSet;
+    /// # use wools::Word;
+    /// assert!(Word::new("apple").as_str() == "apple");
+    /// assert_eq!("saute", Word::new("SAUTÉ").as_str());
+    ///
+    /// let banned = HashSet::from(["crane", "plumb"]);
+    /// assert!(banned.contains(Word::new("CRANE").as_str()));
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.word
+    }
+
     /// Returns an iterator over the normalized characters of the word.
     pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
         self.word.chars()
     }
+
+    /// Returns an iterator over the normalized characters of the word along with their position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// let word = Word::new("apple");
+    /// let positions = word.indexed().filter(|(_, c)| *c == 'p').map(|(i, _)| i);
+    ///
+    /// assert_eq!(vec![1, 2], positions.collect::<Vec<usize>>());
+    /// ```
+    pub fn indexed(&self) -> impl Iterator<Item = (usize, char)> + '_ {
+        self.word.chars().enumerate()
+    }
+
+    /// Returns the pattern of the word guessed against itself, where every hint is green.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert!(Word::new("apple").self_pattern().is_solved());
+    /// ```
+    pub fn self_pattern(&self) -> Pattern {
+        Pattern::from_guess_and_hints(self, &vec![Hint::Green; self.length()])
+    }
+
+    /// Returns the pattern revealed when guessing the word against the solution, like
+    /// [`Pattern::from_solution_and_guess`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::{Hint, Word};
+    /// let pattern = Word::new("crane").reveal_against(&Word::new("trace"));
+    ///
+    /// assert_eq!(Word::new("crane"), pattern.guess);
+    /// assert_eq!(
+    ///     [Hint::Yellow, Hint::Green, Hint::Green, Hint::Black, Hint::Green],
+    ///     *pattern.hints
+    /// );
+    /// ```
+    pub fn reveal_against(&self, solution: &Word) -> Pattern {
+        Pattern::from_solution_and_guess(solution, self)
+    }
+
+    /// Shifts every character of the word by `n` letters in the alphabet, as done by a Caesar
+    /// cipher. Shifting wraps around from `'z'` to `'a'`, so shifting by 26 returns the same word.
+    /// Letters other than `a` to `z` are kept as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert_eq!(Word::new("uryyb"), Word::new("hello").shift(13));
+    /// assert_eq!(Word::new("bcdea"), Word::new("abcdz").shift(1));
+    /// ```
+    pub fn shift(&self, n: u8) -> Word {
+        let (word, letters) = self
+            .chars()
+            .zip(&self.letters)
+            .map(|(c, &letter)| {
+                if c.is_ascii_lowercase() {
+                    let letter = (letter + n % 26) % 26;
+                    ((b'a' + letter) as char, letter)
+                } else {
+                    (c, letter)
+                }
+            })
+            .unzip();
+
+        Word::from_letters(word, letters)
+    }
+
+    /// Counts the positions at which the words have different characters, i.e. the Hamming
+    /// distance between the words.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the words have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert_eq!(0, Word::new("crane").hamming(&Word::new("crane")));
+    /// assert_eq!(1, Word::new("fight").hamming(&Word::new("might")));
+    /// assert_eq!(5, Word::new("crane").hamming(&Word::new("fight")));
+    /// ```
+    pub fn hamming(&self, other: &Word) -> usize {
+        assert_eq!(
+            self.length(),
+            other.length(),
+            "words {} and {} have different lengths",
+            self,
+            other
+        );
+
+        self.letters
+            .iter()
+            .zip(&other.letters)
+            .filter(|(first, second)| first != second)
+            .count()
+    }
+
+    /// Counts the letters the words have in common wherever they are, a letter appearing several
+    /// times in both words being counted as many times as it appears in the word having the
+    /// fewest copies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert_eq!(5, Word::new("stare").shares_letters(&Word::new("tears")));
+    /// assert_eq!(3, Word::new("geese").shares_letters(&Word::new("eerie")));
+    /// assert_eq!(0, Word::new("crane").shares_letters(&Word::new("fight")));
+    /// ```
+    pub fn shares_letters(&self, other: &Word) -> usize {
+        let mut counts = [0_usize; Alphabet::MAX_LETTERS];
+
+        for &letter in &self.letters {
+            counts[letter as usize] += 1;
+        }
+
+        other
+            .letters
+            .iter()
+            .filter(|&&letter| {
+                let count = &mut counts[letter as usize];
+                let shared = *count > 0;
+                *count = count.saturating_sub(1);
+                shared
+            })
+            .count()
+    }
+
+    /// Returns the letters of the word in alphabetical order, which is the same for every anagram
+    /// of the word, e.g. to group a dictionary by anagram class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert_eq!("below", Word::new("elbow").sorted_letters());
+    /// assert_eq!("aelpp", Word::new("apple").sorted_letters());
+    /// ```
+    pub fn sorted_letters(&self) -> String {
+        let mut letters = self.chars().collect::<Vec<char>>();
+        letters.sort_unstable();
+        letters.into_iter().collect()
+    }
+
+    /// Returns whether the words are made of the same letters, each appearing as many times in
+    /// both words. A word is an anagram of itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert!(Word::new("below").is_anagram_of(&Word::new("elbow")));
+    /// assert!(!Word::new("llama").is_anagram_of(&Word::new("lamma")));
+    /// ```
+    pub fn is_anagram_of(&self, other: &Word) -> bool {
+        self.length() == other.length()
+            && self.mask == other.mask
+            && self.shares_letters(other) == self.length()
+    }
+
+    /// Returns the letters of the word as indices in the alphabet, from 0 for `'a'` to 25 for
+    /// `'z'`, other letters having the indices given by their [`Alphabet`]. Comparing letters is
+    /// cheaper than comparing characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert_eq!([2, 17, 0, 13, 4], Word::new("crane").letters());
+    /// ```
+    pub fn letters(&self) -> &[u8] {
+        &self.letters
+    }
+
+    /// Returns the set of letters of the word as a mask, where bit `i` is set when the `i`-th
+    /// letter of the alphabet appears in the word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert_eq!(0b111101, Word::new("faced").mask());
+    /// ```
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    /// Returns whether the letter appears in the word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert!(Word::new("apple").contains('p'));
+    /// assert!(!Word::new("apple").contains('z'));
+    /// assert!(!Word::new("apple").contains('P'));
+    /// ```
+    pub fn contains(&self, c: char) -> bool {
+        if c.is_ascii_lowercase() && self.is_ascii() {
+            self.mask & 1 << (c as u8 - b'a') != 0
+        } else {
+            self.word.contains(c)
+        }
+    }
+
+    /// Counts how many times the letter appears in the word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert_eq!(2, Word::new("apple").count('p'));
+    /// assert_eq!(1, Word::new("apple").count('a'));
+    /// assert_eq!(0, Word::new("apple").count('z'));
+    /// ```
+    pub fn count(&self, c: char) -> usize {
+        self.word.chars().filter(|&other| other == c).count()
+    }
+
+    /// Returns the index of the letter as stored in [`Word::letters`], which is known for the
+    /// letters of the word, and for every letter from `a` to `z` when the word is only made of such
+    /// letters, or `None` for other letters.
+    pub(crate) fn letter(&self, c: char) -> Option<u8> {
+        if c.is_ascii_lowercase() && self.is_ascii() {
+            Some(c as u8 - b'a')
+        } else {
+            self.chars()
+                .zip(&self.letters)
+                .find(|(other, _)| *other == c)
+                .map(|(_, &letter)| letter)
+        }
+    }
+
+    /// Returns whether the word is only made of letters from `a` to `z`, whose indices are the same
+    /// in every alphabet, so that they are known even for letters the word does not have. Letters
+    /// are single characters, so this is the case when there is one byte per letter.
+    pub(crate) fn is_ascii(&self) -> bool {
+        self.word.len() == self.letters.len()
+    }
+
+    /// Writes the word for [`Word::read_from`] as a byte holding the length of the word in UTF-8
+    /// bytes, its UTF-8 bytes, then its letters, one byte per character, so that words of any
+    /// alphabet are read back with the same letters.
+    pub(crate) fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let length = u8::try_from(self.word.len()).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("word {} is longer than 255 bytes", self),
+            )
+        })?;
+
+        writer.write_all(&[length])?;
+        writer.write_all(self.word.as_bytes())?;
+        writer.write_all(&self.letters)
+    }
+
+    /// Reads a word written by [`Word::write_to`].
+    ///
+    /// Returns an error if the word cannot be read, is not normalized, or if its letters are not
+    /// those of an alphabet, such as a letter from `a` to `z` with another index.
+    pub(crate) fn read_from<R: Read>(reader: &mut R) -> Result<Word, String> {
+        let mut length = [0; 1];
+        reader
+            .read_exact(&mut length)
+            .map_err(|err| err.to_string())?;
+
+        let mut word = vec![0; length[0] as usize];
+        reader
+            .read_exact(&mut word)
+            .map_err(|err| err.to_string())?;
+        let word = String::from_utf8(word).map_err(|err| err.to_string())?;
+
+        let mut letters = vec![0; word.chars().count()];
+        reader
+            .read_exact(&mut letters)
+            .map_err(|err| err.to_string())?;
+
+        let mut alphabet = HashMap::new();
+
+        for (c, &letter) in word.chars().zip(&letters) {
+            let known = alphabet
+                .insert(c, letter)
+                .is_none_or(|other| other == letter);
+
+            if !c.is_alphabetic() || c.to_lowercase().ne([c]) {
+                return Err(WordsError::NonAlphabetic.to_string());
+            } else if !known
+                || letter as usize >= Alphabet::MAX_LETTERS
+                || (c.is_ascii_lowercase() && letter != c as u8 - b'a')
+                || alphabet
+                    .iter()
+                    .any(|(&other, &index)| other != c && index == letter)
+            {
+                return Err(format!(
+                    "letters of word {} are not those of an alphabet",
+                    word
+                ));
+            }
+        }
+
+        Ok(Word::from_letters(word, letters))
+    }
+
+    /// Precomputes the letters and the mask of a word which is already normalized.
+    fn from_normalized(word: String) -> Self {
+        let letters = word.bytes().map(|c| c - b'a').collect::<Vec<u8>>();
+        Word::from_letters(word, letters)
+    }
+
+    /// Precomputes the mask of a word from its letters, one per character of the word.
+    fn from_letters(word: String, letters: Vec<u8>) -> Self {
+        let mask = letters.iter().fold(0, |mask, letter| mask | 1 << letter);
+
+        Word {
+            word,
+            letters,
+            mask,
+        }
+    }
 }
 
 impl FromStr for Word {
-    type Err = String;
+    type Err = WordsError;
 
     /// Creates a new word from a string. Normalizes the word in the process, making it lowercase,
-    /// and transliterating some characters.
+    /// and transliterating some characters. Leading and trailing whitespace, e.g. from a copied
+    /// word, is ignored.
     ///
     /// Returns an error if the provided word:
     /// * has a length which is not exactly [`Word::SIZE`];
-    /// * contains non-transliterable characters such as `'`.
+    /// * contains non-transliterable characters such as `'`, or whitespace between its letters.
     ///
     /// # Examples
     ///
@@ -71,31 +571,97 @@ impl FromStr for Word {
     /// assert_eq!(String::from("saute"), word.to_string());
     /// ```
     fn from_str(word: &str) -> Result<Self, Self::Err> {
-        if word.chars().count() != Word::SIZE {
-            return Err(format!("word is not {}-character long", Word::SIZE));
+        Word::with_length(word, Word::SIZE)
+    }
+}
+
+impl TryFrom<[char; Word::SIZE]> for Word {
+    type Error = WordsError;
+
+    /// Creates a new word from its characters, without going through a string. Uppercase ASCII
+    /// letters are lowercased, but other characters are not transliterated like
+    /// [`Word::from_str`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert_eq!(Ok(Word::new("apple")), Word::try_from(['a', 'p', 'P', 'l', 'e']));
+    /// assert!(Word::try_from(['a', 'p', 'p', 'l', 'é']).is_err());
+    /// ```
+    fn try_from(chars: [char; Word::SIZE]) -> Result<Self, Self::Error> {
+        if chars.iter().all(char::is_ascii_alphabetic) {
+            Word::try_from(chars.map(|c| c as u8).as_slice())
+        } else {
+            Err(WordsError::NonAlphabetic)
         }
+    }
+}
 
-        let word = word
-            .to_lowercase()
-            .chars()
-            .map(|c| match c {
-                'é' | 'ê' | 'ë' => 'e',
-                'ó' | 'ô' | 'ö' => 'o',
-                'à' => 'a',
-                'ü' => 'u',
-                'ñ' => 'n',
-                c => c,
-            })
-            .collect::<String>();
+impl TryFrom<&[u8]> for Word {
+    type Error = WordsError;
 
-        if word.chars().all(|c| matches!(c, 'a'..='z')) {
-            Ok(Word { word })
+    /// Creates a new word from ASCII bytes, without going through a string. Uppercase letters are
+    /// lowercased.
+    ///
+    /// Returns an error if there are not exactly [`Word::SIZE`] bytes, or if a byte is not an
+    /// ASCII letter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// assert_eq!(Ok(Word::new("crane")), Word::try_from(b"CRANE".as_slice()));
+    /// assert!(Word::try_from(b"cran".as_slice()).is_err());
+    /// assert!(Word::try_from(b"cr4ne".as_slice()).is_err());
+    /// ```
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != Word::SIZE {
+            return Err(WordsError::WrongLength {
+                expected: Word::SIZE,
+                actual: bytes.len(),
+            });
+        }
+
+        if bytes.iter().all(u8::is_ascii_alphabetic) {
+            let word = bytes.to_ascii_lowercase();
+            // The bytes are ASCII letters, so they are valid UTF-8.
+            Ok(Word::from_normalized(String::from_utf8(word).unwrap()))
         } else {
-            Err("word contains non-alphabetical characters".to_string())
+            Err(WordsError::NonAlphabetic)
         }
     }
 }
 
+impl PartialOrd for Word {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Word {
+    /// Compares the words alphabetically, once normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use wools::Word;
+    /// let mut words = vec![Word::new("Plumb"), Word::new("crane"), Word::new("ÉCLAT")];
+    /// words.sort();
+    ///
+    /// assert_eq!(vec![Word::new("crane"), Word::new("eclat"), Word::new("plumb")], words);
+    /// ```
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.word.cmp(&other.word)
+    }
+}
+
+impl AsRef<str> for Word {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl Display for Word {
     /// Formats the [`Word`] into a `String`.
     ///
@@ -112,11 +678,52 @@ impl Display for Word {
     }
 }
 
+/// Serializes the word as its normalized string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Word {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.word)
+    }
+}
+
+/// Deserializes a word from a string, validated and normalized like [`Word::from_str`] does, except
+/// that the word may have any length, as given by the string.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Word {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let word = String::deserialize(deserializer)?;
+        let length = word.trim().chars().count();
+
+        Word::with_length(&word, length).map_err(serde::de::Error::custom)
+    }
+}
+
+fn transliterate(c: char) -> char {
+    match c {
+        'é' | 'ê' | 'ë' => 'e',
+        'ó' | 'ô' | 'ö' => 'o',
+        'à' => 'a',
+        'ü' => 'u',
+        'ñ' => 'n',
+        c => c,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::str::FromStr;
 
-    use crate::Word;
+    use crate::{solve, Alphabet, Hint, IndexedDictionary, Pattern, Word, WordsError};
+
+    fn spanish(words: &[&str]) -> Vec<Word> {
+        let alphabet = Alphabet::new(('a'..='z').chain(['ñ'])).unwrap();
+
+        words
+            .iter()
+            .map(|word| Word::with_alphabet(word, 5, &alphabet).unwrap())
+            .collect()
+    }
 
     #[test]
     fn given_word_is_too_short_when_from_str_then_return_error() {
@@ -144,6 +751,50 @@ mod tests {
         assert_eq!("oaunx", Word::from_str("öàüñx").unwrap().to_string());
     }
 
+    #[test]
+    fn given_word_has_surrounding_whitespace_when_from_str_then_trim_whitespace() {
+        assert_eq!("apple", Word::from_str(" apple ").unwrap().to_string());
+        assert_eq!("apple", Word::from_str("\tAPPLE\n").unwrap().to_string());
+    }
+
+    #[test]
+    fn given_word_contains_whitespace_when_from_str_then_return_error() {
+        assert!(Word::from_str("ap ple").is_err());
+        assert!(Word::from_str("appl e").is_err());
+    }
+
+    #[test]
+    fn given_word_has_the_length_when_with_length_then_return_word() {
+        assert_eq!("rust", Word::with_length("RUST", 4).unwrap().to_string());
+        assert_eq!("saute", Word::with_length("sauté", 5).unwrap().to_string());
+    }
+
+    #[test]
+    fn given_word_does_not_have_the_length_when_with_length_then_return_error() {
+        assert_eq!(
+            Err(WordsError::WrongLength {
+                expected: 4,
+                actual: 5
+            }),
+            Word::with_length("rusty", 4)
+        );
+        assert_eq!(
+            Err(WordsError::WrongLength {
+                expected: 5,
+                actual: 6
+            }),
+            Word::with_length("cranes", 5)
+        );
+    }
+
+    #[test]
+    fn given_word_contains_apostrophe_when_with_length_then_return_non_alphabetic() {
+        assert_eq!(
+            Err(WordsError::NonAlphabetic),
+            Word::with_length("bob's", 5)
+        );
+    }
+
     #[test]
     fn when_chars_then_return_iterator_over_chars() {
         let word = Word::new("apple");
@@ -157,8 +808,268 @@ mod tests {
         assert_eq!(None, iter.next());
     }
 
+    #[test]
+    fn given_shift_by_one_when_shift_then_letters_are_next_in_alphabet() {
+        assert_eq!(Word::new("afcsb"), Word::new("zebra").shift(1));
+    }
+
+    #[test]
+    fn given_shift_by_twenty_five_when_shift_then_letters_are_previous_in_alphabet() {
+        assert_eq!(Word::new("ydaqz"), Word::new("zebra").shift(25));
+    }
+
+    #[test]
+    fn given_shift_by_twenty_six_when_shift_then_word_is_unchanged() {
+        assert_eq!(Word::new("zebra"), Word::new("zebra").shift(26));
+        assert_eq!(Word::new("afcsb"), Word::new("zebra").shift(27));
+    }
+
+    #[test]
+    fn when_self_pattern_then_every_hint_is_green_and_guess_is_word() {
+        let word = Word::new("apple");
+        let pattern = word.self_pattern();
+
+        assert_eq!(word, pattern.guess);
+        assert_eq!([Hint::Green; Word::SIZE], *pattern.hints);
+        assert_eq!(
+            Pattern::from_solution_and_guess(&word, &word).hints,
+            pattern.hints
+        );
+    }
+
+    #[test]
+    fn when_as_ref_then_return_normalized_word() {
+        fn len<S: AsRef<str>>(s: S) -> usize {
+            s.as_ref().len()
+        }
+
+        assert_eq!(5, len(Word::new("sauté")));
+        assert_eq!("saute", Word::new("sauté").as_ref() as &str);
+    }
+
     #[test]
     fn when_format_then_return_normalized_word() {
         assert_eq!("apple", format!("{}", Word::new("apple")));
     }
+
+    #[test]
+    fn given_table_when_from_str_with_map_then_replace_characters_before_length_check() {
+        let table = HashMap::from([('ß', "ss"), ('æ', "ae"), ('\'', "")]);
+
+        assert_eq!(
+            Word::new("masse"),
+            Word::from_str_with_map("maße", &table).unwrap()
+        );
+        assert_eq!(
+            Word::new("aesir"),
+            Word::from_str_with_map("Æsir", &table).unwrap()
+        );
+        assert_eq!(
+            Word::new("shant"),
+            Word::from_str_with_map("shan't", &table).unwrap()
+        );
+    }
+
+    #[test]
+    fn given_character_not_in_table_when_from_str_with_map_then_use_default_transliteration() {
+        let table = HashMap::from([('é', "a")]);
+
+        assert_eq!(
+            Word::new("sauta"),
+            Word::from_str_with_map("sauté", &table).unwrap()
+        );
+        assert_eq!(
+            Word::new("pinon"),
+            Word::from_str_with_map("piñon", &table).unwrap()
+        );
+        assert!(Word::from_str_with_map("smørs", &table).is_err());
+    }
+
+    #[test]
+    fn given_words_differing_at_some_positions_when_hamming_then_count_those_positions() {
+        assert_eq!(1, Word::new("stare").hamming(&Word::new("store")));
+        assert_eq!(3, Word::new("stare").hamming(&Word::new("tears")));
+    }
+
+    #[test]
+    #[should_panic(expected = "different lengths")]
+    fn given_different_lengths_when_hamming_then_panic() {
+        Word::new("crane").hamming(&Word::with_length("cranes", 6).unwrap());
+    }
+
+    #[test]
+    fn given_repeated_letters_when_shares_letters_then_count_fewest_copies() {
+        assert_eq!(4, Word::new("llama").shares_letters(&Word::new("allay")));
+        assert_eq!(1, Word::new("mamma").shares_letters(&Word::new("crane")));
+        assert_eq!(
+            Word::new("geese").shares_letters(&Word::new("eerie")),
+            Word::new("eerie").shares_letters(&Word::new("geese"))
+        );
+    }
+
+    #[test]
+    fn given_anagrams_when_is_anagram_of_then_return_true() {
+        assert!(Word::new("below").is_anagram_of(&Word::new("elbow")));
+        assert!(Word::new("elbow").is_anagram_of(&Word::new("bowel")));
+        assert!(Word::new("stare").is_anagram_of(&Word::new("stare")));
+        assert_eq!(
+            Word::new("below").sorted_letters(),
+            Word::new("elbow").sorted_letters()
+        );
+    }
+
+    #[test]
+    fn given_same_letters_with_different_counts_when_is_anagram_of_then_return_false() {
+        assert!(!Word::new("llama").is_anagram_of(&Word::new("lamma")));
+        assert!(!Word::new("below").is_anagram_of(&Word::new("blows")));
+        assert!(!Word::new("below").is_anagram_of(&Word::with_length("bellow", 6).unwrap()));
+    }
+
+    #[test]
+    fn when_cmp_then_compare_normalized_words_alphabetically() {
+        assert!(Word::new("apple") < Word::new("apply"));
+        assert!(Word::new("Zebra") > Word::new("apple"));
+        assert!(Word::with_length("rust", 4).unwrap() < Word::new("rusty"));
+        assert_eq!(
+            std::cmp::Ordering::Equal,
+            Word::new("SAUTÉ").cmp(&Word::new("saute"))
+        );
+    }
+
+    #[test]
+    fn when_try_from_chars_or_bytes_then_word_is_the_same_as_from_str() {
+        let word = Word::from_str("apple").unwrap();
+
+        assert_eq!(Ok(word.clone()), Word::try_from(['a', 'p', 'p', 'l', 'e']));
+        assert_eq!(Ok(word), Word::try_from(b"APPLE".as_slice()));
+    }
+
+    #[test]
+    fn given_non_alphabetical_characters_when_try_from_then_return_error() {
+        assert_eq!(
+            Err(WordsError::NonAlphabetic),
+            Word::try_from(['b', 'o', 'b', '\'', 's'])
+        );
+        assert_eq!(
+            Err(WordsError::NonAlphabetic),
+            Word::try_from(b"bob's".as_slice())
+        );
+        assert!(Word::try_from("sauté".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn given_alphabet_when_with_alphabet_then_only_accept_its_letters() {
+        let alphabet = Alphabet::new(('a'..='z').chain(['ñ'])).unwrap();
+        let word = Word::with_alphabet(" AÑEJO ", 5, &alphabet).unwrap();
+
+        assert_eq!("añejo", word.as_str());
+        assert_eq!(5, word.length());
+        assert_eq!(
+            vec!['a', 'ñ', 'e', 'j', 'o'],
+            word.chars().collect::<Vec<char>>()
+        );
+        assert!(word.contains('ñ'));
+        assert_eq!(1, word.count('ñ'));
+        assert_eq!("aejoñ", word.sorted_letters());
+        assert_eq!(
+            Err(WordsError::NonAlphabetic),
+            Word::with_alphabet("sauté", 5, &alphabet)
+        );
+        assert_eq!(
+            Err(WordsError::WrongLength {
+                expected: 5,
+                actual: 4
+            }),
+            Word::with_alphabet("niño", 5, &alphabet)
+        );
+    }
+
+    #[test]
+    fn given_words_of_another_alphabet_when_solve_then_hints_use_their_letters() {
+        let words = spanish(&["niñas", "sueño", "señor", "baños", "pañal"]);
+        let pattern = words[0].reveal_against(&words[3]);
+        let guesses_and_hints = [(pattern.guess.clone(), pattern.hints.clone())];
+
+        assert_eq!(
+            vec![
+                Hint::Black,
+                Hint::Black,
+                Hint::Green,
+                Hint::Yellow,
+                Hint::Green
+            ],
+            pattern.hints
+        );
+        assert_eq!(vec![&words[3]], solve(&words, &guesses_and_hints));
+        assert_eq!(
+            vec![&words[3]],
+            IndexedDictionary::new(words.clone()).solve(&guesses_and_hints)
+        );
+    }
+
+    #[test]
+    fn given_absent_letters_of_another_alphabet_when_solve_then_words_with_them_are_ruled_out() {
+        let alphabet = Alphabet::new("абвгдежзийклмнопрстуфхцчшщъыьэюя".chars()).unwrap();
+        let words = ["слово", "книга", "почта"]
+            .map(|word| Word::with_alphabet(word, 5, &alphabet).unwrap());
+
+        assert_eq!(
+            vec![&words[1]],
+            solve(&words, &[(words[0].clone(), [Hint::Black; 5])])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn when_serialize_and_deserialize_then_word_is_the_same() {
+        let words = vec![Word::new("crane"), Word::with_length("plumbs", 6).unwrap()];
+        let json = serde_json::to_string(&words).unwrap();
+
+        assert_eq!(r#"["crane","plumbs"]"#, json);
+        assert_eq!(words, serde_json::from_str::<Vec<Word>>(&json).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn given_invalid_word_when_deserialize_then_return_error_or_normalize() {
+        assert_eq!(
+            Word::new("crane"),
+            serde_json::from_str::<Word>(r#""CRANE""#).unwrap()
+        );
+        assert!(serde_json::from_str::<Word>(r#""bob's""#).is_err());
+        assert!(serde_json::from_str::<Word>("42").is_err());
+    }
+
+    #[test]
+    fn given_word_of_another_alphabet_when_contains_latin_letter_then_return_false() {
+        let russian = Alphabet::new("абвгдежзийклмнопрстуфхцчшщъыьэюя".chars()).unwrap();
+        let word = Word::with_alphabet("книга", 5, &russian).unwrap();
+
+        assert!(word.contains('а'));
+        assert!(!word.contains('a'));
+        assert!(!word.contains('b'));
+        assert_eq!(None, word.letter('a'));
+        assert_eq!(Some(0), word.letter('а'));
+    }
+
+    #[test]
+    fn given_word_of_another_alphabet_when_write_to_and_read_from_then_word_is_the_same() {
+        let russian = Alphabet::new("абвгдежзийклмнопрстуфхцчшщъыьэюя".chars()).unwrap();
+        let word = Word::with_alphabet("книга", 5, &russian).unwrap();
+        let mut bytes = Vec::new();
+
+        word.write_to(&mut bytes).unwrap();
+
+        assert_eq!(10, bytes[0]);
+        assert_eq!(Ok(word), Word::read_from(&mut bytes.as_slice()));
+    }
+
+    #[test]
+    fn given_letters_are_not_those_of_an_alphabet_when_read_from_then_return_error() {
+        assert!(Word::read_from(&mut b"\x05crane\x02\x11\x00\x0d\x04".as_slice()).is_ok());
+        assert!(Word::read_from(&mut b"\x05crane\x02\x11\x01\x0d\x04".as_slice()).is_err());
+        assert!(Word::read_from(&mut b"\x05crane\x02\x11\x00\x0d\x02".as_slice()).is_err());
+        assert!(Word::read_from(&mut b"\x05CRANE\x02\x11\x00\x0d\x04".as_slice()).is_err());
+        assert!(Word::read_from(&mut b"\x05crane\x02\x11\x00\x0d".as_slice()).is_err());
+    }
 }
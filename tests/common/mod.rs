@@ -0,0 +1,36 @@
+//! Helpers shared by the integration tests, each of which only uses some of them.
+#![allow(dead_code, unused_imports)]
+
+mod temp;
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+pub use temp::TempFile;
+
+/// Runs the binary with the arguments, writing `stdin` to its standard input, and waits for it to
+/// exit.
+pub fn wools(args: &[&str], stdin: &str) -> Output {
+    wools_with_env(args, &[], stdin)
+}
+
+/// Runs the binary like [`wools`], with the environment variables set.
+pub fn wools_with_env(args: &[&str], vars: &[(&str, &str)], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wools"))
+        .args(args)
+        .envs(vars.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
@@ -0,0 +1,47 @@
+//! A temporary file for the tests, shared by the integration tests and the unit tests reading
+//! files, each of which only uses some of its methods.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+/// A file of the temporary directory, removed once dropped, even when the test fails.
+pub struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    /// Creates the file holding the contents. The name is prefixed with the process id, so that
+    /// concurrent test runs do not share files, and must be unique among the tests.
+    pub fn new(name: &str, contents: &str) -> Self {
+        let file = TempFile::missing(name);
+        std::fs::write(&file.path, contents).unwrap();
+        file
+    }
+
+    /// Reserves the path of a file which does not exist yet, e.g. for a file the test writes.
+    pub fn missing(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("wools-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_file(&path);
+        TempFile { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path as a string, e.g. to pass it as an argument.
+    pub fn as_str(&self) -> &str {
+        self.path.to_str().unwrap()
+    }
+
+    /// Replaces the contents of the file.
+    pub fn write(&self, contents: &str) {
+        std::fs::write(&self.path, contents).unwrap();
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use common::wools;
+
+#[test]
+fn when_count_then_print_number_of_remaining_words() {
+    let output = wools(
+        &[
+            "count",
+            "soare,bbbbb",
+            "unlit,bbbyg",
+            "fight,bgggg",
+            "bowed,bbbbb",
+        ],
+        "",
+    );
+
+    assert!(output.status.success());
+    assert_eq!("5\n", String::from_utf8_lossy(&output.stdout));
+}
+
+#[test]
+fn given_no_word_remains_when_count_then_print_zero_and_succeed() {
+    let output = wools(&["count", "zzzzz,ggggg"], "");
+
+    assert!(output.status.success());
+    assert_eq!("0\n", String::from_utf8_lossy(&output.stdout));
+}
+
+#[test]
+fn given_invalid_guess_when_count_then_fail() {
+    let output = wools(&["count", "fight"], "");
+
+    assert!(!output.status.success());
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::{wools, TempFile};
+
+#[test]
+fn given_scattered_duplicates_when_dict_then_keep_first_occurrences_in_order() {
+    let path = TempFile::new("dict.txt", "crane\nplumb\nCRANE\nfight\nplumb\ncrane\n");
+
+    let output = wools(&["--dictionary", path.as_str(), "dict"], "");
+
+    assert!(output.status.success());
+    assert_eq!(
+        "crane\nplumb\nfight\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn given_sorted_when_dict_then_print_words_alphabetically() {
+    let path = TempFile::new("unsorted.txt", "plumb\ncrane\nfight\nCRANE\nabbey\n");
+
+    let output = wools(&["--dictionary", path.as_str(), "--sorted", "dict"], "");
+
+    assert!(output.status.success());
+    assert_eq!(
+        "abbey\ncrane\nfight\nplumb\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn given_frequencies_and_sorted_when_solve_then_print_words_alphabetically() {
+    let path = TempFile::new(
+        "sorted.txt",
+        "sight 3\nlight 20\ncrane 50\nmight\nfight 20\n",
+    );
+
+    let output = wools(
+        &[
+            "--dictionary",
+            path.as_str(),
+            "--sorted",
+            "solve",
+            "tight,bgggg",
+        ],
+        "",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "fight\nlight\nmight\nsight\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn given_sort_after_dict_when_dict_then_print_words_alphabetically() {
+    let path = TempFile::new("sort.txt", "plumb\ncrane\nabbey\n");
+
+    let output = wools(&["--dictionary", path.as_str(), "dict", "--sort"], "");
+
+    assert!(output.status.success());
+    assert_eq!(
+        "abbey\ncrane\nplumb\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn given_stats_when_dict_then_print_counts() {
+    let path = TempFile::new("stats.txt", "geese\ncrane\nGEESE\nllama\n");
+
+    let output = wools(&["--dictionary", path.as_str(), "dict", "--stats"], "");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout.lines().collect::<Vec<_>>();
+
+    assert!(output.status.success());
+    assert_eq!(
+        [
+            "words: 3",
+            "unique words: 3",
+            "words with repeated letters: 2"
+        ],
+        lines[..3]
+    );
+    assert_eq!("a: 3", lines[3]);
+    assert_eq!("e: 4", lines[7]);
+    assert_eq!("z: 0", lines[28]);
+}
+
+#[test]
+fn given_invalid_frequency_when_dict_then_report_line() {
+    let path = TempFile::new("bad-frequency.txt", "crane 42\npotato\nplumb often\n");
+
+    let output = wools(&["--dictionary", path.as_str(), "dict"], "");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("line 3: frequency often"));
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use common::wools;
+
+#[test]
+fn given_excluded_words_when_solve_then_leave_them_out() {
+    let output = wools(
+        &[
+            "solve",
+            "soare,bbbbb",
+            "unlit,bbbyg",
+            "fight,bgggg",
+            "bowed,bbbbb",
+            "--exclude",
+            "MIGHT,tight",
+        ],
+        "",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "hight\nkight\npight\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn given_excluded_words_when_filter_then_leave_them_out() {
+    let output = wools(
+        &["filter", "fight", "light", "--exclude", "might,sight"],
+        "",
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.lines().any(|word| word == "fight"));
+    assert!(!stdout
+        .lines()
+        .any(|word| word == "might" || word == "sight"));
+}
@@ -0,0 +1,23 @@
+mod common;
+
+use common::wools;
+
+#[test]
+fn when_explain_then_print_whether_each_guess_rules_out_the_word() {
+    let output = wools(&["explain", "might", "crane,bbbbb", "tiger,ggbbb"], "");
+
+    assert!(output.status.success());
+    assert_eq!(
+        "crane,bbbbb: possible\n\
+         tiger,ggbbb: ruled out, has forbidden 'g' at position 2, has absent letter 'g', \
+         missing 't' at position 0\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn given_invalid_guess_when_explain_then_fail() {
+    let output = wools(&["explain", "might", "crane"], "");
+
+    assert!(!output.status.success());
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::wools;
+
+#[test]
+fn given_json_format_when_match_then_print_array_of_words() {
+    let output = wools(&["--format", "json", "match", "fight", "ggggg"], "");
+
+    assert!(output.status.success());
+    assert_eq!("[\"fight\"]\n", String::from_utf8_lossy(&output.stdout));
+}
+
+#[test]
+fn given_json_format_when_solve_then_print_remaining_count_and_words() {
+    let output = wools(
+        &[
+            "--format",
+            "json",
+            "solve",
+            "soare,bbbbb",
+            "unlit,bbbyg",
+            "fight,bgggg",
+            "bowed,bbbbb",
+        ],
+        "",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "{\"remaining_count\":5,\"words\":[\"hight\",\"kight\",\"might\",\"pight\",\"tight\"]}\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn given_json_format_after_the_command_when_match_then_print_array_of_words() {
+    let output = wools(&["match", "fight", "ggggg", "--format", "json"], "");
+
+    assert!(output.status.success());
+    assert_eq!("[\"fight\"]\n", String::from_utf8_lossy(&output.stdout));
+}
+
+#[test]
+fn given_no_format_when_solve_then_print_one_word_per_line() {
+    let plain = wools(
+        &[
+            "solve",
+            "soare,bbbbb",
+            "unlit,bbbyg",
+            "fight,bgggg",
+            "bowed,bbbbb",
+        ],
+        "",
+    );
+    let explicit = wools(
+        &[
+            "--format",
+            "plain",
+            "solve",
+            "soare,bbbbb",
+            "unlit,bbbyg",
+            "fight,bgggg",
+            "bowed,bbbbb",
+        ],
+        "",
+    );
+
+    assert_eq!(
+        "hight\nkight\nmight\npight\ntight\n",
+        String::from_utf8_lossy(&plain.stdout)
+    );
+    assert_eq!(plain.stdout, explicit.stdout);
+}
+
+#[test]
+fn given_color_always_when_match_then_print_colored_tiles() {
+    let output = wools(&["match", "fight", "ggggg", "--color", "always"], "");
+
+    assert!(output.status.success());
+    assert_eq!(
+        "\x1b[1;97;42m F \x1b[0m\x1b[1;97;42m I \x1b[0m\x1b[1;97;42m G \x1b[0m\
+         \x1b[1;97;42m H \x1b[0m\x1b[1;97;42m T \x1b[0m\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn given_stdout_is_not_a_terminal_when_match_then_print_plain_words() {
+    let output = wools(&["match", "fight", "ggggg"], "");
+
+    assert!(output.status.success());
+    assert_eq!("fight\n", String::from_utf8_lossy(&output.stdout));
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::{wools, TempFile};
+
+#[test]
+fn given_more_words_than_the_limit_when_dict_then_tell_how_many_more_on_stderr() {
+    let path = TempFile::new("limit.txt", "crane\nplumb\nfight\nmight\n");
+
+    let dict = |limit| {
+        wools(
+            &["--dictionary", path.as_str(), "--limit", limit, "dict"],
+            "",
+        )
+    };
+    let truncated = dict("2");
+    let exact = dict("4");
+    let unlimited = dict("0");
+
+    assert!(truncated.status.success());
+    assert_eq!("crane\nplumb\n", String::from_utf8_lossy(&truncated.stdout));
+    assert_eq!("... (2 more)\n", String::from_utf8_lossy(&truncated.stderr));
+    assert_eq!(
+        "crane\nplumb\nfight\nmight\n",
+        String::from_utf8_lossy(&exact.stdout)
+    );
+    assert!(exact.stderr.is_empty());
+    assert_eq!(exact.stdout, unlimited.stdout);
+    assert!(unlimited.stderr.is_empty());
+}
+
+#[test]
+fn given_limit_when_solve_as_json_then_keep_the_remaining_count() {
+    let path = TempFile::new("limit-json.txt", "fight\nlight\nmight\ncrane\n");
+
+    let output = wools(
+        &[
+            "--dictionary",
+            path.as_str(),
+            "--format",
+            "json",
+            "--limit",
+            "1",
+            "solve",
+            "tight,bgggg",
+        ],
+        "",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "{\"remaining_count\":3,\"words\":[\"fight\"]}\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    assert_eq!("... (2 more)\n", String::from_utf8_lossy(&output.stderr));
+}
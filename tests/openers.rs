@@ -0,0 +1,54 @@
+mod common;
+
+use common::{wools, TempFile};
+
+#[cfg(feature = "serde")]
+#[test]
+fn given_cache_when_openers_then_read_it_until_the_dictionary_changes() {
+    let dictionary = TempFile::new("openers-words.txt", "fight\nmight\ncrane\nplumb\n");
+    let cache = TempFile::missing("openers.json");
+
+    let openers = || {
+        wools(
+            &[
+                "--dictionary",
+                dictionary.as_str(),
+                "openers",
+                "--top",
+                "2",
+                "--cache",
+                cache.as_str(),
+            ],
+            "",
+        )
+    };
+    let computed = openers();
+    let saved = std::fs::read_to_string(cache.path()).unwrap();
+    cache.write(&saved.replace("2.0", "9.0"));
+    let cached = openers();
+    dictionary.write("fight\nmight\ncrane\n");
+    let recomputed = openers();
+
+    assert!(computed.status.success());
+    assert_eq!(
+        "might  2.00 bits\nfight  1.50 bits\n",
+        String::from_utf8_lossy(&computed.stdout)
+    );
+    assert_eq!(
+        "might  9.00 bits\nfight  1.50 bits\n",
+        String::from_utf8_lossy(&cached.stdout)
+    );
+    assert!(!String::from_utf8_lossy(&recomputed.stdout).contains("9.00"));
+}
+
+#[cfg(not(feature = "serde"))]
+#[test]
+fn given_cache_without_serde_when_openers_then_fail() {
+    let cache = TempFile::missing("no-serde.json");
+
+    let output = wools(&["openers", "--top", "2", "--cache", cache.as_str()], "");
+
+    assert!(!output.status.success());
+    assert!(!cache.path().exists());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires the serde feature"));
+}
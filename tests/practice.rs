@@ -0,0 +1,51 @@
+mod common;
+
+use common::wools_with_env;
+
+#[test]
+fn given_solution_variable_when_practice_then_play_against_solution() {
+    let output = wools_with_env(
+        &["practice"],
+        &[("WOOLS_SOLUTION", "apple")],
+        "prime\napple\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "ybbbg (5 tries left)\nggggg (4 tries left)\nSolved in 2 guesses\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn given_solution_variable_is_not_a_word_when_practice_then_fail() {
+    let output = wools_with_env(&["practice"], &[("WOOLS_SOLUTION", "potato")], "");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("WOOLS_SOLUTION"));
+}
+
+#[test]
+fn given_timed_when_practice_then_report_timings_after_solving() {
+    let output = wools_with_env(
+        &["practice", "--timed"],
+        &[("WOOLS_SOLUTION", "apple")],
+        "prime\napple\n",
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout.lines().collect::<Vec<_>>();
+
+    assert!(output.status.success());
+    assert_eq!(
+        [
+            "ybbbg (5 tries left)",
+            "ggggg (4 tries left)",
+            "Solved in 2 guesses"
+        ],
+        lines[..3]
+    );
+    assert!(lines[3].starts_with("Guess 1: ") && lines[3].ends_with('s'));
+    assert!(lines[4].starts_with("Guess 2: ") && lines[4].ends_with('s'));
+    assert!(lines[5].starts_with("Total time: ") && lines[5].ends_with('s'));
+    assert_eq!(6, lines.len());
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::{wools, TempFile};
+
+#[test]
+fn given_rounds_when_solve_interactively_then_report_candidates_until_solved() {
+    let output = wools(
+        &["solve", "--interactive", "soare,bbbbb"],
+        "unlit,bbbyg\nnight,bgggg\nfight,ggggg\n",
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout.lines().collect::<Vec<_>>();
+
+    assert!(output.status.success());
+    assert_eq!(3, lines.len());
+    assert!(lines[0].starts_with("14 candidates left, try "));
+    assert!(lines[1].starts_with("9 candidates left, try "));
+    assert_eq!("The solution is fight", lines[2]);
+}
+
+#[test]
+fn given_invalid_line_when_solve_interactively_then_report_error_and_continue() {
+    let output = wools(&["solve", "--interactive"], "crane\ncrane,ggggg\n");
+
+    assert!(output.status.success());
+    assert_eq!(
+        "The solution is crane\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    assert!(!output.stderr.is_empty());
+}
+
+#[test]
+fn given_end_of_input_when_solve_interactively_then_stop_gracefully() {
+    let output = wools(&["solve", "--interactive"], "");
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn given_contradictory_hints_when_solve_then_report_conflicting_constraints() {
+    let output = wools(&["solve", "light,ggggg", "sight,ggggg"], "");
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("position 0 must be 'l' but position 0 must be 's'"));
+}
+
+#[test]
+fn given_history_file_when_solve_then_use_its_guesses_with_the_arguments() {
+    let path = TempFile::new("history.txt", "soare,bbbbb\n\nunlit,bbbyg\n");
+    let output = wools(&["solve", "--from-file", path.as_str(), "night,bgggg"], "");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.lines().any(|line| line == "fight"));
+    assert!(!stdout.lines().any(|line| line == "night"));
+}
+
+#[test]
+fn given_history_from_stdin_when_solve_then_use_its_guesses() {
+    let output = wools(
+        &["solve", "--from-file", "-"],
+        "soare,bbbbb\nunlit,bbbyg\nnight,bgggg\nfight,ggggg\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!("fight\n", String::from_utf8_lossy(&output.stdout));
+}
+
+#[test]
+fn given_invalid_line_in_history_file_when_solve_then_report_line_number() {
+    let path = TempFile::new("invalid-history.txt", "soare,bbbbb\nunlit\n");
+    let output = wools(&["solve", "--from-file", path.as_str()], "");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("line 2: "));
+}
+
+#[test]
+fn given_guess_of_wrong_length_when_solve_then_suggest_closest_dictionary_words() {
+    let path = TempFile::new("closest-dictionary.txt", "plumb\ncrane\ncrate\n");
+    let output = wools(
+        &["--dictionary", path.as_str(), "solve", "cranes,bbbbb"],
+        "",
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'crane', 'crate'"));
+}
+
+#[test]
+fn given_answers_and_allowed_guesses_when_solve_then_only_answers_remain() {
+    let answers = TempFile::new("answers.txt", "fight\nsight\ncrane\n");
+    let allowed = TempFile::new("allowed.txt", "light\nmight\n");
+
+    let solve = |guess: &str| {
+        wools(
+            &[
+                "--answers",
+                answers.as_str(),
+                "--allowed",
+                allowed.as_str(),
+                "solve",
+                guess,
+            ],
+            "",
+        )
+    };
+    let output = solve("light,bgggg");
+    let rejected = solve("tight,bgggg");
+
+    assert!(output.status.success());
+    assert_eq!("fight\nsight\n", String::from_utf8_lossy(&output.stdout));
+    assert!(!rejected.status.success());
+    assert!(String::from_utf8_lossy(&rejected.stderr).contains("not an allowed guess"));
+}
+
+#[test]
+fn given_frequencies_when_solve_then_print_most_frequent_words_first() {
+    let path = TempFile::new(
+        "frequencies.txt",
+        "fight 3\nlight 20\ncrane 50\nmight\nsight 20\n",
+    );
+
+    let output = wools(&["--dictionary", path.as_str(), "solve", "tight,bgggg"], "");
+
+    assert!(output.status.success());
+    assert_eq!(
+        "light\nsight\nfight\nmight\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
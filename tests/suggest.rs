@@ -0,0 +1,51 @@
+mod common;
+
+use common::wools;
+
+#[test]
+fn given_history_when_suggest_then_print_top_guesses_with_scores() {
+    let output = wools(&["suggest", "soare,bbbbb", "unlit,bbbyg", "--top", "3"], "");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert_eq!(3, stdout.lines().count());
+    assert!(stdout.lines().all(|line| line.ends_with(" bits")));
+    assert_eq!("champ  2.40 bits", stdout.lines().next().unwrap());
+}
+
+#[test]
+fn given_metric_when_suggest_then_score_guesses_in_words() {
+    let minimax = wools(
+        &[
+            "suggest",
+            "soare,bbbbb",
+            "unlit,bbbyg",
+            "--top",
+            "1",
+            "--metric",
+            "minimax",
+        ],
+        "",
+    );
+    let average = wools(
+        &[
+            "suggest",
+            "soare,bbbbb",
+            "unlit,bbbyg",
+            "--top",
+            "1",
+            "--metric",
+            "average",
+        ],
+        "",
+    );
+
+    assert_eq!(
+        "dimbo  6.00 words\n",
+        String::from_utf8_lossy(&minimax.stdout)
+    );
+    assert_eq!(
+        "dimps  3.57 words\n",
+        String::from_utf8_lossy(&average.stdout)
+    );
+}